@@ -0,0 +1,43 @@
+//! Web builds have no asset folder to expand `manifest.ron` globs in at runtime,
+//! so they bake in a copy with every `Glob` replaced by the `List` of files it matches.
+
+use std::{env, fs, path::Path};
+
+#[path = "src/glob.rs"]
+mod glob;
+
+fn main() {
+    if env::var("CARGO_CFG_TARGET_ARCH").as_deref() != Ok("wasm32") {
+        return;
+    }
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets");
+    let manifest = root.join("manifest.ron");
+    println!("cargo:rerun-if-changed={}", manifest.display());
+    let content = fs::read_to_string(&manifest)
+        .unwrap_or_else(|e| panic!("could not read {}: {e}", manifest.display()));
+
+    let mut expanded = String::new();
+    let mut rest = content.as_str();
+    while let Some(start) = rest.find("Glob(\"") {
+        expanded.push_str(&rest[..start]);
+        let glob = &rest[start + "Glob(\"".len()..];
+        let end = glob.find("\")").expect("manifest.ron: unterminated Glob");
+        let pattern = &glob[..end];
+        let folder = pattern.rsplit_once('/').map_or("", |(folder, _)| folder);
+        println!("cargo:rerun-if-changed={}", root.join(folder).display());
+
+        let paths = glob::expand_glob(&root, pattern)
+            .unwrap_or_else(|e| panic!("manifest.ron: could not expand '{pattern}': {e}"));
+        if paths.is_empty() {
+            println!("cargo:warning=manifest.ron: '{pattern}' matches no files");
+        }
+        let paths: Vec<_> = paths.iter().map(|path| format!("{path:?}")).collect();
+        expanded.push_str(&format!("List([{}])", paths.join(", ")));
+        rest = &glob[end + "\")".len()..];
+    }
+    expanded.push_str(rest);
+
+    let out =
+        Path::new(&env::var("OUT_DIR").expect("OUT_DIR is set by cargo")).join("manifest.ron");
+    fs::write(&out, expanded).unwrap_or_else(|e| panic!("could not write {}: {e}", out.display()));
+}
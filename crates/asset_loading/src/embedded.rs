@@ -0,0 +1,140 @@
+//! Splash, UI icons and the UI font compiled into the binary and served from the
+//! `embedded://` asset source, so splash and menus render without waiting on the disk.
+//!
+//! With the `file_ui_assets` feature they are loaded from the asset folder instead,
+//! to hot reload them in dev.
+
+use super::*;
+use bevy::{
+    asset::{AssetPath, io::embedded::EMBEDDED as EMBEDDED_SOURCE},
+    image::{ImageLoaderSettings, ImageSampler},
+};
+#[cfg(not(feature = "file_ui_assets"))]
+use {
+    bevy::asset::io::embedded::EmbeddedAssetRegistry,
+    std::path::{Path, PathBuf},
+};
+
+/// Asset paths and bytes of everything in [`UiAssets`]
+#[cfg(not(feature = "file_ui_assets"))]
+const EMBEDDED: &[(&str, &[u8])] = &[
+    (
+        UiAssets::SPLASH,
+        include_bytes!("../../../assets/textures/bevy.png"),
+    ),
+    (
+        UiAssets::PAUSE,
+        include_bytes!("../../../assets/textures/pause.png"),
+    ),
+    (
+        UiAssets::MUTE,
+        include_bytes!("../../../assets/textures/mute.png"),
+    ),
+    (
+        UiAssets::GITHUB,
+        include_bytes!("../../../assets/textures/github.png"),
+    ),
+    (
+        UiAssets::FONT,
+        include_bytes!("../../../assets/fonts/DejaVuSansMono.ttf"),
+    ),
+];
+
+pub(super) fn plugin(app: &mut App) {
+    // Inserted by hand instead of `embedded_asset!`, which derives the path from the source file
+    // and is [broken on Windows Wasm builds](https://github.com/bevyengine/bevy/issues/14246).
+    #[cfg(not(feature = "file_ui_assets"))]
+    {
+        let registry = app.world().resource::<EmbeddedAssetRegistry>();
+        for (path, bytes) in EMBEDDED {
+            let full_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("../../assets")
+                .join(path);
+            registry.insert_asset(full_path, Path::new(path), *bytes);
+        }
+    }
+
+    app.init_resource::<UiAssets>()
+        .add_systems(Update, replace_default_font);
+}
+
+#[derive(Resource, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct UiAssets {
+    pub splash: Handle<Image>,
+    pub pause: Handle<Image>,
+    pub mute: Handle<Image>,
+    pub github: Handle<Image>,
+    /// Replaces the default font once loaded, so every `TextFont` uses it
+    pub font: Handle<Font>,
+}
+
+impl UiAssets {
+    pub const SPLASH: &'static str = "textures/bevy.png";
+    pub const PAUSE: &'static str = "textures/pause.png";
+    pub const MUTE: &'static str = "textures/mute.png";
+    pub const GITHUB: &'static str = "textures/github.png";
+    pub const FONT: &'static str = "fonts/DejaVuSansMono.ttf";
+
+    fn path(path: &'static str) -> AssetPath<'static> {
+        let path = AssetPath::from(path);
+        if cfg!(feature = "file_ui_assets") {
+            path
+        } else {
+            path.with_source(EMBEDDED_SOURCE)
+        }
+    }
+}
+
+impl FromWorld for UiAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            splash: assets.load_with_settings(
+                Self::path(Self::SPLASH),
+                |settings: &mut ImageLoaderSettings| {
+                    // Make an exception for the splash image in case
+                    // `ImagePlugin::default_nearest()` is used for pixel art.
+                    settings.sampler = ImageSampler::linear();
+                },
+            ),
+            pause: assets.load(Self::path(Self::PAUSE)),
+            mute: assets.load(Self::path(Self::MUTE)),
+            github: assets.load(Self::path(Self::GITHUB)),
+            font: assets.load(Self::path(Self::FONT)),
+        }
+    }
+}
+
+fn replace_default_font(
+    ui: Res<UiAssets>,
+    mut events: EventReader<AssetEvent<Font>>,
+    mut fonts: ResMut<Assets<Font>>,
+) {
+    for event in events.read() {
+        if !event.is_loaded_with_dependencies(&ui.font) && !event.is_modified(&ui.font) {
+            continue;
+        }
+        if let Some(font) = fonts.get(&ui.font).cloned() {
+            fonts.insert(AssetId::default(), font);
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "file_ui_assets")))]
+mod tests {
+    use super::*;
+
+    fn embedded(path: &str) -> &'static [u8] {
+        EMBEDDED
+            .iter()
+            .find(|(embedded, _)| *embedded == path)
+            .map(|(_, bytes)| *bytes)
+            .unwrap()
+    }
+
+    #[test]
+    fn embedded_font_parses() {
+        assert!(Font::try_from_bytes(embedded(UiAssets::FONT).to_vec()).is_ok());
+    }
+}
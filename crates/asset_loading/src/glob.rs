@@ -0,0 +1,69 @@
+//! File name globs of `manifest.ron`, shared with `build.rs` which expands them for web builds.
+
+use std::{io, path::Path};
+
+/// Sorted paths relative to `root` of the files matching `pattern`,
+/// `*` and `?` are allowed in the file name only
+pub fn expand_glob(root: &Path, pattern: &str) -> io::Result<Vec<String>> {
+    let (folder, file) = pattern.rsplit_once('/').unwrap_or(("", pattern));
+    let mut paths = vec![];
+    for entry in std::fs::read_dir(root.join(folder))? {
+        let Ok(name) = entry?.file_name().into_string() else {
+            continue;
+        };
+        if glob_match(file.as_bytes(), name.as_bytes()) {
+            paths.push(match folder {
+                "" => name,
+                _ => format!("{folder}/{name}"),
+            });
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, None) => true,
+        (Some((b'*', rest)), _) => {
+            glob_match(rest, name) || (!name.is_empty() && glob_match(pattern, &name[1..]))
+        }
+        (Some((b'?', rest)), Some((_, name))) => glob_match(rest, name),
+        (Some((p, rest)), Some((n, name))) => p == n && glob_match(rest, name),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::glob_match;
+
+    fn matches(pattern: &str, name: &str) -> bool {
+        glob_match(pattern.as_bytes(), name.as_bytes())
+    }
+
+    #[test]
+    fn star_matches_any_run() {
+        assert!(matches("*.ogg", "step1.ogg"));
+        assert!(matches("*.ogg", ".ogg"));
+        assert!(matches("step*.ogg", "step_snow_12.ogg"));
+        assert!(matches("*", ""));
+        assert!(!matches("*.ogg", "step1.wav"));
+        assert!(!matches("step*.ogg", "snow_step1.ogg"));
+    }
+
+    #[test]
+    fn question_mark_matches_one_byte() {
+        assert!(matches("step?.ogg", "step1.ogg"));
+        assert!(!matches("step?.ogg", "step.ogg"));
+        assert!(!matches("step?.ogg", "step12.ogg"));
+        assert!(matches("step??.ogg", "step12.ogg"));
+    }
+
+    #[test]
+    fn literal_patterns_match_exactly() {
+        assert!(matches("theme.ogg", "theme.ogg"));
+        assert!(!matches("theme.ogg", "theme.ogg.bak"));
+        assert!(!matches("theme.ogg", "Theme.ogg"));
+    }
+}
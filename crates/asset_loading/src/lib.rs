@@ -1,122 +1,118 @@
 // use crate::prelude::*;
-use bevy::{asset::Asset, prelude::*};
+use bevy::{
+    asset::{Asset, UntypedAssetId, VisitAssetDependencies},
+    platform::collections::HashMap,
+    prelude::*,
+};
 use bevy_seedling::sample::Sample;
 // use bevy_shuffle_bag::ShuffleBag;
-use models::Config;
+use models::{AnimationSet, Config, StepKind, SurfaceMaterial, TutorialSteps, WeatherSet};
 
+mod embedded;
+#[cfg(not(target_arch = "wasm32"))]
+mod glob;
+mod manifest;
 mod ron;
 mod tracking;
 
+pub use embedded::*;
+pub use manifest::*;
 pub use ron::*;
 pub use tracking::*;
 
 pub fn plugin(app: &mut App) {
     // start asset loading
-    //app.load_resource_from_path::<Fira>("fonts/FiraCode-Regular.ttf");
-    app.add_plugins(tracking::plugin)
-        .add_plugins(RonAssetPlugin::<Config>::new(&["config.ron"]))
+    let manifest = AssetManifest::read().unwrap_or_else(|e| {
+        error!("{e}");
+        AssetManifest::default()
+    });
+    for problem in manifest.validate() {
+        error!("{}: {problem}", AssetManifest::PATH);
+    }
+    app.insert_resource(manifest)
+        .add_plugins((tracking::plugin, embedded::plugin))
+        .add_plugins(RonValuePlugin)
+        .add_plugins(RonAssetPlugin::<Config>::new(&["config.ron"]).with_dependencies())
+        .add_plugins(RonAssetPlugin::<AnimationSet>::new(&["animations.ron"]))
+        .add_plugins(RonAssetPlugin::<TutorialSteps>::new(&["tutorial.ron"]))
+        .add_plugins(RonAssetPlugin::<WeatherSet>::new(&["weather.ron"]))
         .load_resource_from_path::<Config>("config.ron")
+        .load_resource_from_path::<AnimationSet>("animations.ron")
+        .load_resource_from_path::<TutorialSteps>("tutorial.ron")
+        .load_resource_from_path::<WeatherSet>("weather.ron")
         .load_resource::<AudioSources>()
-        .load_resource::<Textures>()
+        .load_resource::<FootstepSounds>()
         .load_resource::<Models>();
 }
 
-#[derive(Asset, Clone, Reflect, Resource)]
-#[reflect(Resource)]
-pub struct Fonts {
-    #[dependency]
-    pub fira: Handle<Font>,
-}
-
-#[derive(Asset, Clone, Reflect, Resource)]
-#[reflect(Resource)]
-pub struct Textures {
-    #[dependency]
-    pub github: Handle<Image>,
-    #[dependency]
-    pub pause: Handle<Image>,
-    #[dependency]
-    pub mute: Handle<Image>,
+asset_collection! {
+    pub struct Models("models") {
+        player: Handle<Gltf>,
+        scene: Handle<Gltf>,
+        ..props: AssetMap<Gltf>,
+    }
 }
 
-impl FromWorld for Textures {
-    fn from_world(world: &mut World) -> Self {
-        let assets = world.resource::<AssetServer>();
-        Self {
-            github: assets.load("textures/github.png"),
-            pause: assets.load("textures/pause.png"),
-            mute: assets.load("textures/mute.png"),
-        }
+asset_collection! {
+    pub struct AudioSources("audio") {
+        btn_hover: Handle<Sample>,
+        btn_press: Handle<Sample>,
     }
 }
 
-#[derive(Asset, Clone, Reflect, Resource)]
-#[reflect(Resource)]
-pub struct Models {
-    #[dependency]
-    pub player: Handle<Gltf>,
-    #[dependency]
-    pub rock: Handle<Gltf>,
-    #[dependency]
-    pub caged_matter: Handle<Gltf>,
-    #[dependency]
-    pub comb_sphere: Handle<Gltf>,
-    #[dependency]
-    pub scene: Handle<Gltf>,
+/// Footstep clips per [`SurfaceMaterial`] and [`StepKind`] from the `footsteps` collection
+/// of `manifest.ron`. Walking clips are named after the surface, the other kinds
+/// `<surface>_<kind>`, e.g. `snow_crouch`. Every entry but `stone` is optional.
+#[derive(TypePath, Clone, Resource)]
+pub struct FootstepSounds(HashMap<(SurfaceMaterial, StepKind), Vec<Handle<Sample>>>);
+
+impl FootstepSounds {
+    /// Falls back to the walking clips of the surface, then to the stone ones
+    pub fn clips(&self, surface: SurfaceMaterial, kind: StepKind) -> &[Handle<Sample>] {
+        let stone = SurfaceMaterial::Stone;
+        [
+            (surface, kind),
+            (surface, StepKind::Walk),
+            (stone, kind),
+            (stone, StepKind::Walk),
+        ]
+        .iter()
+        .find_map(|key| self.0.get(key))
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+    }
 }
 
-impl FromWorld for Models {
+impl FromWorld for FootstepSounds {
     fn from_world(world: &mut World) -> Self {
+        let manifest = world.resource::<AssetManifest>();
         let assets = world.resource::<AssetServer>();
-        Self {
-            caged_matter: assets.load("models/caged_matter.glb"),
-            comb_sphere: assets.load("models/comb_sphere.glb"),
-            player: assets.load("models/player.glb"),
-            rock: assets.load("models/rock.glb"),
-            scene: assets.load("models/scene.glb"),
+        let mut clips = HashMap::default();
+        for surface in SurfaceMaterial::ALL {
+            for kind in StepKind::ALL {
+                let name = match kind {
+                    StepKind::Walk => surface.as_str().to_string(),
+                    _ => format!("{}_{}", surface.as_str(), kind.as_str()),
+                };
+                if let Some(paths) = manifest.get("footsteps", &name) {
+                    let handles = paths.iter().map(|path| assets.load(path)).collect();
+                    clips.insert((surface, kind), handles);
+                }
+            }
         }
+        if !clips.contains_key(&(SurfaceMaterial::Stone, StepKind::Walk)) {
+            error!("{}: missing 'footsteps.stone'", AssetManifest::PATH);
+        }
+        Self(clips)
     }
 }
 
-#[derive(Asset, Clone, Reflect, Resource)]
-#[reflect(Resource)]
-pub struct AudioSources {
-    // SFX
-    #[dependency]
-    pub btn_hover: Handle<Sample>,
-    #[dependency]
-    pub btn_press: Handle<Sample>,
-    #[dependency]
-    pub steps: Vec<Handle<Sample>>,
-
-    // music
-    #[dependency]
-    pub bg_music: Handle<Sample>,
-}
-
-impl AudioSources {
-    pub const STEPS: &[&'static str] = &[
-        "audio/sfx/step.ogg",
-        "audio/sfx/step1.ogg",
-        "audio/sfx/step2.ogg",
-        "audio/sfx/step3.ogg",
-        "audio/sfx/step4.ogg",
-    ];
-    pub const BTN_HOVER: &'static str = "audio/sfx/btn-hover.ogg";
-    pub const BTN_PRESS: &'static str = "audio/sfx/btn-press.ogg";
+impl Asset for FootstepSounds {}
 
-    pub const BG_MUSIC: &'static str = "audio/music/smnbl-time-for-fun.ogg";
-}
-
-impl FromWorld for AudioSources {
-    fn from_world(world: &mut World) -> Self {
-        let assets = world.resource::<AssetServer>();
-        let steps = Self::STEPS.iter().map(|p| assets.load(*p)).collect();
-        Self {
-            steps,
-            btn_hover: assets.load(Self::BTN_HOVER),
-            btn_press: assets.load(Self::BTN_PRESS),
-            bg_music: assets.load(Self::BG_MUSIC),
+impl VisitAssetDependencies for FootstepSounds {
+    fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId)) {
+        for handle in self.0.values().flatten() {
+            visit(handle.id().untyped());
         }
     }
 }
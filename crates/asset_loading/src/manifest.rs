@@ -0,0 +1,362 @@
+//! Asset manifest: every file the game loads on startup, grouped into named collections.
+//!
+//! Collections are declared in code with [`asset_collection!`](crate::asset_collection) and
+//! get their paths from `assets/manifest.ron`. Entries the code refers to have a field,
+//! the rest are looked up by key in an [`AssetMap`], so adding a model or a footstep variant
+//! is a manifest edit only.
+
+use super::*;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// One named entry of a collection in `manifest.ron`
+#[derive(Deserialize, Debug, Clone)]
+pub enum ManifestEntry {
+    /// A single file
+    File(String),
+    /// Several files in a fixed order
+    List(Vec<String>),
+    /// Files in a folder matching the pattern, `*` and `?` are allowed in the file name only
+    Glob(String),
+}
+
+/// Possible errors when reading `manifest.ron`
+#[derive(Debug, Error)]
+pub enum ManifestError {
+    #[error("Could not read {path}: {error}")]
+    Io { path: String, error: std::io::Error },
+    #[error("Could not parse {path}:{error}")]
+    Ron {
+        path: String,
+        error: ::ron::error::SpannedError,
+    },
+}
+
+/// Manifest with every entry resolved to a list of asset paths
+#[derive(Resource, Default, Debug)]
+pub struct AssetManifest {
+    collections: HashMap<String, HashMap<String, Vec<String>>>,
+}
+
+impl AssetManifest {
+    pub const PATH: &'static str = "manifest.ron";
+
+    /// Reads the manifest from the asset folder and expands globs.
+    /// On web there is no folder to read, so `build.rs` bakes the manifest in with globs expanded.
+    pub fn read() -> Result<Self, ManifestError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        return Self::read_from(&asset_root());
+        #[cfg(target_arch = "wasm32")]
+        Self::parse(
+            include_str!(concat!(env!("OUT_DIR"), "/manifest.ron")),
+            &asset_root(),
+        )
+    }
+
+    /// Reads the manifest of an asset folder other than the one the game loads from
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_from(root: &Path) -> Result<Self, ManifestError> {
+        let path = root.join(Self::PATH);
+        let content = std::fs::read_to_string(&path).map_err(|error| ManifestError::Io {
+            path: path.display().to_string(),
+            error,
+        })?;
+        Self::parse(&content, root)
+    }
+
+    fn parse(content: &str, root: &Path) -> Result<Self, ManifestError> {
+        let entries: HashMap<String, HashMap<String, ManifestEntry>> = ::ron::from_str(content)
+            .map_err(|error| ManifestError::Ron {
+                path: Self::PATH.to_string(),
+                error,
+            })?;
+
+        let collections = entries
+            .into_iter()
+            .map(|(collection, entries)| {
+                let entries = entries
+                    .into_iter()
+                    .map(|(name, entry)| {
+                        let paths = match entry {
+                            ManifestEntry::File(path) => vec![path],
+                            ManifestEntry::List(paths) => paths,
+                            ManifestEntry::Glob(pattern) => expand_glob(root, &pattern),
+                        };
+                        (name, paths)
+                    })
+                    .collect();
+                (collection, entries)
+            })
+            .collect();
+
+        Ok(Self { collections })
+    }
+
+    /// Problems with the manifest: empty entries and files that do not exist
+    pub fn validate(&self) -> Vec<String> {
+        self.validate_in(&asset_root())
+    }
+
+    /// Same as [`AssetManifest::validate`] against an asset folder other than the one the game loads from
+    #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+    pub fn validate_in(&self, root: &Path) -> Vec<String> {
+        let mut problems = vec![];
+        for (collection, entries) in self.collections.iter() {
+            for (name, paths) in entries.iter() {
+                if paths.is_empty() {
+                    problems.push(format!("{collection}.{name}: no files"));
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                for path in paths.iter().filter(|p| !root.join(p).is_file()) {
+                    problems.push(format!("{collection}.{name}: '{path}' does not exist"));
+                }
+            }
+        }
+        problems.sort();
+        problems
+    }
+
+    /// Every asset path in the manifest
+    pub fn all_paths(&self) -> impl Iterator<Item = &str> {
+        self.collections
+            .values()
+            .flat_map(|entries| entries.values())
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Entry names of a collection
+    pub fn names(&self, collection: &str) -> impl Iterator<Item = &str> {
+        self.collections
+            .get(collection)
+            .into_iter()
+            .flat_map(|entries| entries.keys())
+            .map(String::as_str)
+    }
+
+    /// Asset paths of an optional entry
+    pub fn get(&self, collection: &str, name: &str) -> Option<&[String]> {
+        self.collections
+            .get(collection)
+            .and_then(|entries| entries.get(name))
+            .map(Vec::as_slice)
+    }
+
+    /// Asset paths of an entry, empty if the manifest does not have it
+    pub fn paths(&self, collection: &str, name: &str) -> Vec<String> {
+        match self.get(collection, name) {
+            Some(paths) => paths.to_vec(),
+            None => {
+                error!("{}: missing '{collection}.{name}'", Self::PATH);
+                vec![]
+            }
+        }
+    }
+}
+
+/// Field of a collection that can be loaded from the paths of a manifest entry
+pub trait FromManifest {
+    fn from_manifest(paths: Vec<String>, assets: &AssetServer) -> Self;
+}
+
+impl<A: Asset> FromManifest for Handle<A> {
+    fn from_manifest(paths: Vec<String>, assets: &AssetServer) -> Self {
+        if paths.len() > 1 {
+            warn!("expected a single file, using the first of {paths:?}");
+        }
+        paths
+            .into_iter()
+            .next()
+            .map(|path| assets.load(path))
+            .unwrap_or_default()
+    }
+}
+
+impl<A: Asset> FromManifest for Vec<Handle<A>> {
+    fn from_manifest(paths: Vec<String>, assets: &AssetServer) -> Self {
+        paths.into_iter().map(|path| assets.load(path)).collect()
+    }
+}
+
+/// Handles of the manifest entries a collection has no field for, by entry name
+#[derive(Reflect)]
+pub struct AssetMap<A: Asset>(bevy::platform::collections::HashMap<String, Handle<A>>);
+
+// derived `Clone` would require `A: Clone`
+impl<A: Asset> Clone for AssetMap<A> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<A: Asset> AssetMap<A> {
+    /// Loads every entry of the collection but the skipped ones
+    pub fn from_manifest(
+        manifest: &AssetManifest,
+        collection: &str,
+        skip: &[&str],
+        assets: &AssetServer,
+    ) -> Self {
+        let handles = manifest
+            .names(collection)
+            .filter(|name| !skip.contains(name))
+            .map(|name| {
+                let paths = manifest.paths(collection, name);
+                (name.to_string(), Handle::from_manifest(paths, assets))
+            })
+            .collect();
+        Self(handles)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Handle<A>> {
+        self.0.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Handle<A>)> {
+        self.0.iter().map(|(name, handle)| (name.as_str(), handle))
+    }
+}
+
+impl<A: Asset> VisitAssetDependencies for AssetMap<A> {
+    fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId)) {
+        for handle in self.0.values() {
+            visit(handle.id().untyped());
+        }
+    }
+}
+
+/// Declares a collection of asset handles loaded from a `manifest.ron` collection.
+/// Every field is a manifest entry of the same name, either `Handle<A>` or `Vec<Handle<A>>`.
+/// An optional `..name: AssetMap<A>` field holds every other entry of the collection by key.
+/// Register it with [`LoadResource::load_resource`] to have it tracked.
+///
+/// ```ignore
+/// asset_collection! {
+///     pub struct Models("models") {
+///         player: Handle<Gltf>,
+///         ..props: AssetMap<Gltf>,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! asset_collection {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident($collection:literal) {
+            $( $(#[$field_meta:meta])* $field:ident: $ty:ty ),* $(,)?
+            $( ..$rest:ident: $rest_ty:ty $(,)? )?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Asset, Clone, Reflect, Resource)]
+        #[reflect(Resource)]
+        $vis struct $name {
+            $(
+                $(#[$field_meta])*
+                #[dependency]
+                pub $field: $ty,
+            )*
+            $(
+                #[dependency]
+                pub $rest: $rest_ty,
+            )?
+        }
+
+        impl $name {
+            /// Manifest entries with a field of their own
+            pub const FIELDS: &'static [&'static str] = &[$(stringify!($field)),*];
+        }
+
+        impl FromWorld for $name {
+            fn from_world(world: &mut World) -> Self {
+                let manifest = world.resource::<$crate::AssetManifest>();
+                let assets = world.resource::<AssetServer>();
+                Self {
+                    $(
+                        $field: $crate::FromManifest::from_manifest(
+                            manifest.paths($collection, stringify!($field)),
+                            assets,
+                        ),
+                    )*
+                    $(
+                        $rest: $crate::AssetMap::from_manifest(
+                            manifest,
+                            $collection,
+                            Self::FIELDS,
+                            assets,
+                        ),
+                    )?
+                }
+            }
+        }
+    };
+}
+
+/// Folder the game loads assets from, the output of `asset_pipeline` for release builds
+pub const ASSET_FOLDER: &str = if cfg!(feature = "processed") {
+    "processed_assets"
+} else {
+    "assets"
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+fn asset_root() -> PathBuf {
+    bevy::asset::io::file::FileAssetReader::get_base_path().join(ASSET_FOLDER)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn asset_root() -> PathBuf {
+    PathBuf::from(ASSET_FOLDER)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn expand_glob(root: &Path, pattern: &str) -> Vec<String> {
+    crate::glob::expand_glob(root, pattern).unwrap_or_else(|e| {
+        error!("{}: could not expand '{pattern}': {e}", AssetManifest::PATH);
+        vec![]
+    })
+}
+
+/// `build.rs` already replaced every glob of the baked in manifest with its files
+#[cfg(target_arch = "wasm32")]
+fn expand_glob(_root: &Path, pattern: &str) -> Vec<String> {
+    error!(
+        "{}: globs are expanded at build time, rebuild to pick up '{pattern}'",
+        AssetManifest::PATH
+    );
+    vec![]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_are_listed_by_collection() {
+        let manifest = AssetManifest::parse(
+            r#"{
+                "models": {
+                    "player": File("models/player.glb"),
+                    "rock": File("models/rock.glb"),
+                },
+                "footsteps": { "stone": List(["step0.ogg", "step1.ogg"]) },
+            }"#,
+            Path::new("assets"),
+        )
+        .unwrap();
+
+        let mut names: Vec<_> = manifest.names("models").collect();
+        names.sort();
+        assert_eq!(names, ["player", "rock"]);
+        assert_eq!(manifest.names("missing").count(), 0);
+        assert_eq!(
+            manifest.get("footsteps", "stone").unwrap(),
+            ["step0.ogg", "step1.ogg"]
+        );
+        assert!(manifest.get("models", "stone").is_none());
+    }
+}
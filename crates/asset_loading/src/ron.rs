@@ -4,45 +4,58 @@ use bevy::{
     asset::{Asset, AssetApp, AssetLoader, LoadContext, io::Reader},
     prelude::*,
 };
-use std::marker::PhantomData;
+use models::RonDependencies;
+use serde::Deserialize;
 use thiserror::Error;
 
+/// Loads assets referenced from the RON file, see [`RonAssetPlugin::with_dependencies`]
+type LoadDependencies<A> = fn(&mut A, &mut LoadContext);
+
 /// Plugin to load your asset type `A` from ron files.
 pub struct RonAssetPlugin<A> {
     extensions: Vec<&'static str>,
-    _marker: PhantomData<A>,
+    dependencies: LoadDependencies<A>,
 }
 
 impl<A> Plugin for RonAssetPlugin<A>
 where
-    for<'de> A: serde::Deserialize<'de> + Asset,
+    for<'de> A: Deserialize<'de> + Asset,
 {
     fn build(&self, app: &mut App) {
         app.init_asset::<A>()
             .register_asset_loader(RonAssetLoader::<A> {
                 extensions: self.extensions.clone(),
-                _marker: PhantomData,
+                dependencies: self.dependencies,
             });
     }
 }
 
 impl<A> RonAssetPlugin<A>
 where
-    for<'de> A: serde::Deserialize<'de> + Asset,
+    for<'de> A: Deserialize<'de> + Asset,
 {
     /// Create a new plugin that will load assets from files with the given extensions.
     pub fn new(extensions: &[&'static str]) -> Self {
         Self {
             extensions: extensions.to_owned(),
-            _marker: PhantomData,
+            dependencies: |_, _| {},
         }
     }
+
+    /// Loads the [`RonHandle`](models::RonHandle) fields of `A` along with it
+    pub fn with_dependencies(mut self) -> Self
+    where
+        A: RonDependencies,
+    {
+        self.dependencies = A::load_dependencies;
+        self
+    }
 }
 
 /// Loads your asset type `A` from ron files
 pub struct RonAssetLoader<A> {
     extensions: Vec<&'static str>,
-    _marker: PhantomData<A>,
+    dependencies: LoadDependencies<A>,
 }
 
 /// Possible errors that can be produced by [`RonAssetLoader`]
@@ -52,14 +65,26 @@ pub enum RonLoaderError {
     /// An [IO Error](std::io::Error)
     #[error("Could not read the file: {0}")]
     Io(#[from] std::io::Error),
-    /// A [RON Error](ron::error::SpannedError)
-    #[error("Could not parse RON: {0}")]
-    RonError(#[from] ron::error::SpannedError),
+    /// A [RON Error](ron::error::SpannedError) with the file it happened in
+    #[error("Could not parse RON {path}:{error}")]
+    RonError {
+        path: String,
+        error: ron::error::SpannedError,
+    },
+}
+
+impl RonLoaderError {
+    fn parse(load_context: &LoadContext, error: ron::error::SpannedError) -> Self {
+        Self::RonError {
+            path: load_context.path().display().to_string(),
+            error,
+        }
+    }
 }
 
 impl<A> AssetLoader for RonAssetLoader<A>
 where
-    for<'de> A: serde::Deserialize<'de> + Asset,
+    for<'de> A: Deserialize<'de> + Asset,
 {
     type Asset = A;
     type Settings = ();
@@ -69,11 +94,13 @@ where
         &self,
         reader: &mut dyn Reader,
         _settings: &(),
-        _load_context: &mut LoadContext<'_>,
+        load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
-        let asset = ron::de::from_bytes::<A>(&bytes)?;
+        let mut asset =
+            ron::de::from_bytes::<A>(&bytes).map_err(|e| RonLoaderError::parse(load_context, e))?;
+        (self.dependencies)(&mut asset, load_context);
         Ok(asset)
     }
 
@@ -82,90 +109,43 @@ where
     }
 }
 
-// attempt on any ron
-//
-// use std::ops::{Deref, DerefMut};
-// use std::str::from_utf8;
-//
-// use bevy::app::Plugin;
-// use bevy::asset::AssetApp;
-// use bevy::asset::io::Reader;
-// use bevy::{
-//     asset::{Asset, AssetLoader},
-//     reflect::Reflect,
-// };
-// use thiserror::Error;
-//
-// pub type RonValue = ron::Value;
-//
-// /// Representation of any Ron asset
-// #[derive(Asset, Reflect)]
-// pub struct Ron(
-//     // Wrapped with option due to need for default implementation
-//     #[reflect(ignore)] Option<RonValue>,
-// );
-//
-// impl Ron {
-//     pub fn into_inner(self) -> RonValue {
-//         self.0.unwrap()
-//     }
-// }
-//
-// impl Deref for Ron {
-//     type Target = RonValue;
-//
-//     fn deref(&self) -> &Self::Target {
-//         self.0.as_ref().unwrap()
-//     }
-// }
-//
-// impl DerefMut for Ron {
-//     fn deref_mut(&mut self) -> &mut Self::Target {
-//         self.0.as_mut().unwrap()
-//     }
-// }
-//
-// #[derive(Debug, Error)]
-// pub enum RonLoaderError {
-//     /// An [IO Error](std::io::Error)
-//     #[error("Could not read the file: {0}")]
-//     Io(#[from] std::io::Error),
-//     /// A [conversion Error](std::str::Utf8Error)
-//     #[error("Could not interpret as UTF-8: {0}")]
-//     FormatError(#[from] std::str::Utf8Error),
-//     /// A [Ron Error](ron::de::SpannedError)
-//     #[error("Could not parse RON: {0}")]
-//     RonError(#[from] ron::de::SpannedError),
-// }
-//
-// pub struct RonAssetPlugin;
-// impl Plugin for RonAssetPlugin {
-//     fn build(&self, app: &mut bevy::prelude::App) {
-//         app.init_asset::<Ron>().register_asset_loader(RonLoader);
-//     }
-// }
-//
-// #[derive(Default)]
-// struct RonLoader;
-// impl AssetLoader for RonLoader {
-//     type Asset = Ron;
-//     type Settings = ();
-//     type Error = RonLoaderError;
-//
-//     async fn load(
-//         &self,
-//         reader: &mut dyn Reader,
-//         _settings: &Self::Settings,
-//         _load_context: &mut bevy::asset::LoadContext<'_>,
-//     ) -> Result<Self::Asset, Self::Error> {
-//         let mut bytes = Vec::new();
-//         reader.read_to_end(&mut bytes).await?;
-//         let s = from_utf8(&bytes)?;
-//         let asset = ron::de::from_str(s)?;
-//         Ok(Ron(Some(asset)))
-//     }
-//
-//     fn extensions(&self) -> &[&str] {
-//         &["ron"]
-//     }
-// }
+/// Any RON file as a dynamic value, for data that has no type of its own
+#[derive(Asset, TypePath, Debug, Clone, Deref, DerefMut)]
+pub struct Ron(pub ron::Value);
+
+/// Registers [`Ron`] as a fallback for every `.ron` file without a typed loader
+pub struct RonValuePlugin;
+
+impl Plugin for RonValuePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Ron>()
+            .register_asset_loader(RonValueLoader);
+    }
+}
+
+/// Loads any ron file into [`Ron`]
+#[derive(Default)]
+pub struct RonValueLoader;
+
+impl AssetLoader for RonValueLoader {
+    type Asset = Ron;
+    type Settings = ();
+    type Error = RonLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let value = ron::de::from_bytes::<ron::Value>(&bytes)
+            .map_err(|e| RonLoaderError::parse(load_context, e))?;
+        Ok(Ron(value))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
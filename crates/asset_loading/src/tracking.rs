@@ -1,11 +1,55 @@
 //! A high-level way to load collections of asset handles as resources.
 
 use super::*;
+use bevy::{
+    asset::{LoadState, UntypedAssetId, VisitAssetDependencies},
+    platform::collections::HashSet,
+};
 use std::{collections::VecDeque, path::Path};
 
 pub(super) fn plugin(app: &mut App) {
-    app.init_resource::<ResourceHandles>();
-    app.add_systems(PreUpdate, load_resource_assets);
+    app.init_resource::<ResourceHandles>()
+        .init_resource::<ModifiedAssets>();
+    app.add_systems(PreUpdate, load_resource_assets)
+        .add_systems(Last, clear_modified_assets);
+
+    // Asset types that collections loaded with `load_resource` depend on
+    #[cfg(feature = "dev_native")]
+    app.add_systems(
+        PreUpdate,
+        (
+            collect_modified_assets::<Gltf>,
+            collect_modified_assets::<Image>,
+            collect_modified_assets::<Font>,
+            collect_modified_assets::<Sample>,
+        )
+            .in_set(HotReloadSet::Collect),
+    )
+    .configure_sets(
+        PreUpdate,
+        (HotReloadSet::Collect, HotReloadSet::Refresh).chain(),
+    );
+}
+
+/// Ordering of dependency-aware hot reload
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HotReloadSet {
+    /// Gather assets modified on disk into [`ModifiedAssets`]
+    Collect,
+    /// Mark resources with modified dependencies as changed
+    Refresh,
+}
+
+/// Assets that got modified on disk this frame.
+/// Dependants that cache something out of an asset (spawned scenes and such) can check it
+/// to update themselves.
+#[derive(Resource, Default)]
+pub struct ModifiedAssets(pub HashSet<UntypedAssetId>);
+
+impl ModifiedAssets {
+    pub fn contains(&self, id: impl Into<UntypedAssetId>) -> bool {
+        self.0.contains(&id.into())
+    }
 }
 
 pub trait LoadResource {
@@ -28,10 +72,25 @@ impl LoadResource for App {
         self.init_asset::<T>();
         let world = self.world_mut();
         let value = T::from_world(world);
+        // the collection itself is added loaded, progress is tracked per dependency
+        let mut dependencies = vec![];
+        value.visit_dependencies(&mut |id| dependencies.push(id));
         let assets = world.resource::<AssetServer>();
         let handle = assets.add(value);
         let mut handles = world.resource_mut::<ResourceHandles>();
+        handles.tracked.extend(dependencies);
         handles.push_handle(handle);
+
+        // Collection is built in code and never reloads by itself,
+        // so refresh it when any of its dependencies changes
+        #[cfg(feature = "dev_native")]
+        self.add_systems(
+            PreUpdate,
+            refresh_resource::<T>
+                .run_if(resource_exists::<T>)
+                .in_set(HotReloadSet::Refresh),
+        );
+
         self
     }
 
@@ -46,6 +105,7 @@ impl LoadResource for App {
             let handle: Handle<T> = assets.load::<T>(path.as_ref());
             let src_handle = handle.clone();
             let mut handles = world.resource_mut::<ResourceHandles>();
+            handles.tracked.push(handle.id().untyped());
             handles.push_handle(handle);
             src_handle
         };
@@ -84,6 +144,8 @@ pub struct ResourceHandles {
     // `finished` one at a time.
     waiting: VecDeque<(UntypedHandle, InsertLoadedResource)>,
     finished: Vec<UntypedHandle>,
+    /// Every individual asset behind the resources, for progress and error reporting
+    tracked: Vec<UntypedAssetId>,
 }
 
 impl ResourceHandles {
@@ -92,6 +154,57 @@ impl ResourceHandles {
         self.waiting.is_empty()
     }
 
+    /// [`LoadState`] of every tracked asset
+    pub fn load_states<'a>(
+        &'a self,
+        server: &'a AssetServer,
+    ) -> impl Iterator<Item = (UntypedAssetId, LoadState)> + 'a {
+        self.tracked.iter().map(|id| {
+            let state = server.get_load_state(*id).unwrap_or(LoadState::NotLoaded);
+            (*id, state)
+        })
+    }
+
+    /// Number of loaded tracked assets out of their total
+    pub fn progress(&self, server: &AssetServer) -> (usize, usize) {
+        let loaded = self
+            .load_states(server)
+            .filter(|(_, state)| state.is_loaded())
+            .count();
+        (loaded, self.tracked.len())
+    }
+
+    /// Path of the first asset still loading
+    pub fn current(&self, server: &AssetServer) -> Option<String> {
+        self.load_states(server)
+            .find(|(_, state)| state.is_loading())
+            .map(|(id, _)| asset_name(server, id))
+    }
+
+    /// Path and error of every asset that failed to load
+    pub fn failed(&self, server: &AssetServer) -> Vec<(String, String)> {
+        self.load_states(server)
+            .filter_map(|(id, state)| match state {
+                LoadState::Failed(e) => Some((asset_name(server, id), e.to_string())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Reloads every asset that failed, returns the reloaded ones.
+    /// Reloading happens in the background, they stay failed for a few frames.
+    pub fn retry(&self, server: &AssetServer) -> Vec<UntypedAssetId> {
+        let mut retried = vec![];
+        for (id, state) in self.load_states(server) {
+            if let (LoadState::Failed(_), Some(path)) = (state, server.get_path(id)) {
+                info!("retrying to load '{path}'");
+                server.reload(path.into_owned());
+                retried.push(id);
+            }
+        }
+        retried
+    }
+
     /// Adds an asset handle to the list of pending assets to be tracked and converted to resources
     /// on load.
     pub fn push_handle<T: Asset + Resource + Clone>(&mut self, handle: Handle<T>) {
@@ -104,6 +217,42 @@ impl ResourceHandles {
     }
 }
 
+fn asset_name(server: &AssetServer, id: UntypedAssetId) -> String {
+    server
+        .get_path(id)
+        .map(|path| path.to_string())
+        .unwrap_or_else(|| format!("{id:?}"))
+}
+
+#[cfg(feature = "dev_native")]
+fn collect_modified_assets<A: Asset>(
+    mut events: EventReader<AssetEvent<A>>,
+    mut modified: ResMut<ModifiedAssets>,
+) {
+    for event in events.read() {
+        if let AssetEvent::Modified { id } = event {
+            modified.0.insert(id.untyped());
+        }
+    }
+}
+
+#[cfg(feature = "dev_native")]
+fn refresh_resource<T: Resource + Asset>(modified: Res<ModifiedAssets>, mut resource: ResMut<T>) {
+    if modified.0.is_empty() {
+        return;
+    }
+    let mut changed = false;
+    resource.visit_dependencies(&mut |id| changed |= modified.contains(id));
+    if changed {
+        info!("refreshing {}", std::any::type_name::<T>());
+        resource.set_changed();
+    }
+}
+
+fn clear_modified_assets(mut modified: ResMut<ModifiedAssets>) {
+    modified.0.clear();
+}
+
 fn load_resource_assets(world: &mut World) {
     world.resource_scope(|world, mut resource_handles: Mut<ResourceHandles>| {
         world.resource_scope(|world, assets: Mut<AssetServer>| {
@@ -0,0 +1,296 @@
+//! Prepares the asset folder for release builds.
+//!
+//! - validates every path referenced from `manifest.ron` and the other RON files
+//! - compresses manifest textures to KTX2 (UASTC + zstd) with `toktx` from KTX-Software,
+//!   the manifest loads the KTX2 copy and the original is kept next to it
+//! - strips unused data out of glTF models with `gltf-transform`
+//!
+//! Everything else is copied as is into the output folder, which the game loads from
+//! when built with the `processed_assets` feature. Missing tools are reported and the
+//! files they would process are copied unchanged.
+//!
+//! Usage: `cargo run -p asset_pipeline -- [--web] [assets] [processed_assets]`,
+//! `--web` keeps textures as they are since basis-universal does not build for web.
+
+use asset_loading::AssetManifest;
+use std::{
+    collections::HashSet,
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+};
+
+/// Extensions of strings in RON files that are treated as asset paths
+const ASSET_EXTENSIONS: &[&str] = &[
+    "glb", "gltf", "png", "jpg", "jpeg", "ktx2", "ogg", "wav", "ttf", "otf", "ron",
+];
+const TEXTURE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg"];
+const GLTF_EXTENSIONS: &[&str] = &["glb", "gltf"];
+
+fn main() -> ExitCode {
+    let mut web = false;
+    let mut folders = vec![];
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--web" => web = true,
+            _ => folders.push(PathBuf::from(arg)),
+        }
+    }
+    let src = folders.first().cloned().unwrap_or("assets".into());
+    let out = folders.get(1).cloned().unwrap_or("processed_assets".into());
+
+    let manifest = match AssetManifest::read_from(&src) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut problems = manifest.validate_in(&src);
+    problems.extend(validate_ron_references(&src));
+    if !problems.is_empty() {
+        for problem in problems {
+            eprintln!("{problem}");
+        }
+        return ExitCode::FAILURE;
+    }
+
+    match process(&src, &out, &manifest, web) {
+        Ok(()) => {
+            println!("processed {} into {}", src.display(), out.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn process(src: &Path, out: &Path, manifest: &AssetManifest, web: bool) -> io::Result<()> {
+    if out.exists() {
+        fs::remove_dir_all(out)?;
+    }
+    let textures: HashSet<&str> = manifest
+        .all_paths()
+        .filter(|path| !web && has_extension(path, TEXTURE_EXTENSIONS))
+        .collect();
+
+    let mut compressed = vec![];
+    for file in files(src)? {
+        let relative = file.strip_prefix(src).unwrap_or(&file);
+        let target = out.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        if has_extension(&relative, GLTF_EXTENSIONS) {
+            if !run("gltf-transform", &["prune"], &file, &target) {
+                fs::copy(&file, &target)?;
+            }
+        } else if textures.contains(relative.as_str()) {
+            // the original stays for glTF materials and RON files that point at it
+            fs::copy(&file, &target)?;
+            let ktx2 = target.with_extension("ktx2");
+            let args = ["--t2", "--encode", "uastc", "--zcmp", "19", "--genmipmap"];
+            if run_toktx(&args, &file, &ktx2) {
+                compressed.push(relative);
+            }
+        } else {
+            fs::copy(&file, &target)?;
+        }
+    }
+
+    // point the manifest at compressed textures, globs still match the original extension
+    let manifest_path = out.join(AssetManifest::PATH);
+    let mut content = fs::read_to_string(&manifest_path)?;
+    for path in compressed {
+        let ktx2 = Path::new(&path).with_extension("ktx2");
+        let ktx2 = ktx2.to_string_lossy().replace('\\', "/");
+        content = content.replace(&format!("\"{path}\""), &format!("\"{ktx2}\""));
+    }
+    fs::write(manifest_path, content)
+}
+
+/// Strings that look like asset paths in every RON file, besides the manifest, that do not exist
+fn validate_ron_references(src: &Path) -> Vec<String> {
+    let mut problems = vec![];
+    let Ok(files) = files(src) else {
+        return vec![format!("could not read {}", src.display())];
+    };
+    for file in files
+        .iter()
+        .filter(|f| has_extension(&f.to_string_lossy(), &["ron"]))
+    {
+        if file.ends_with(AssetManifest::PATH) {
+            continue;
+        }
+        let value = fs::read_to_string(file)
+            .map_err(|e| e.to_string())
+            .and_then(|content| ron::from_str::<ron::Value>(&content).map_err(|e| e.to_string()));
+        match value {
+            Ok(value) => {
+                let mut strings = vec![];
+                collect_strings(&value, &mut strings);
+                for path in strings
+                    .iter()
+                    .filter(|s| has_extension(s, ASSET_EXTENSIONS))
+                {
+                    let file_path = path.split('#').next().unwrap_or(path);
+                    if !src.join(file_path).is_file() {
+                        problems.push(format!("{}: '{path}' does not exist", file.display()));
+                    }
+                }
+            }
+            Err(e) => problems.push(format!("{}:{e}", file.display())),
+        }
+    }
+    problems
+}
+
+fn collect_strings(value: &ron::Value, strings: &mut Vec<String>) {
+    match value {
+        ron::Value::String(s) => strings.push(s.clone()),
+        ron::Value::Seq(values) => values.iter().for_each(|v| collect_strings(v, strings)),
+        ron::Value::Map(map) => map.iter().for_each(|(k, v)| {
+            collect_strings(k, strings);
+            collect_strings(v, strings);
+        }),
+        ron::Value::Option(Some(value)) => collect_strings(value, strings),
+        _ => {}
+    }
+}
+
+/// Every file under the folder, recursively
+fn files(folder: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in fs::read_dir(folder)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(self::files(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn has_extension(path: &str, extensions: &[&str]) -> bool {
+    let path = path.split('#').next().unwrap_or(path);
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| extensions.contains(&e.to_lowercase().as_str()))
+}
+
+/// `tool <args> <input> <output>`, false if the tool is missing or fails
+fn run(tool: &str, args: &[&str], input: &Path, output: &Path) -> bool {
+    let status = Command::new(tool)
+        .args(args)
+        .arg(input)
+        .arg(output)
+        .status();
+    report(tool, input, status)
+}
+
+/// `toktx` takes the output before the input
+fn run_toktx(args: &[&str], input: &Path, output: &Path) -> bool {
+    let status = Command::new("toktx")
+        .args(args)
+        .arg(output)
+        .arg(input)
+        .status();
+    report("toktx", input, status)
+}
+
+fn report(tool: &str, input: &Path, status: io::Result<std::process::ExitStatus>) -> bool {
+    match status {
+        Ok(status) if status.success() => true,
+        Ok(status) => {
+            eprintln!(
+                "{tool} failed on {}: {status}, copying as is",
+                input.display()
+            );
+            false
+        }
+        Err(e) => {
+            eprintln!(
+                "{tool} unavailable ({e}), copying {} as is",
+                input.display()
+            );
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `src` asset folder with a manifest listing one real PNG, removed on drop
+    struct Fixture(PathBuf);
+
+    impl Fixture {
+        fn new(name: &str) -> Self {
+            let root =
+                env::temp_dir().join(format!("asset_pipeline_{name}_{}", std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            let src = root.join("src");
+            fs::create_dir_all(src.join("textures")).unwrap();
+            let png = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets/textures/icon.png");
+            fs::copy(png, src.join("textures/icon.png")).unwrap();
+            fs::write(
+                src.join(AssetManifest::PATH),
+                r#"{ "textures": { "icon": File("textures/icon.png") } }"#,
+            )
+            .unwrap();
+            Self(root)
+        }
+
+        fn process(&self, web: bool) -> String {
+            let (src, out) = (self.0.join("src"), self.0.join("out"));
+            let manifest = AssetManifest::read_from(&src).unwrap();
+            assert!(manifest.validate_in(&src).is_empty());
+            process(&src, &out, &manifest, web).unwrap();
+            assert!(out.join("textures/icon.png").is_file(), "original is kept");
+            fs::read_to_string(out.join(AssetManifest::PATH)).unwrap()
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn toktx_available() -> bool {
+        Command::new("toktx")
+            .arg("--version")
+            .output()
+            .is_ok_and(|output| output.status.success())
+    }
+
+    #[test]
+    fn manifest_textures_point_at_ktx2_when_compressed() {
+        let fixture = Fixture::new("native");
+        let manifest = fixture.process(false);
+        let ktx2 = fixture.0.join("out/textures/icon.ktx2");
+        if toktx_available() {
+            assert!(ktx2.is_file());
+            assert!(manifest.contains("\"textures/icon.ktx2\""), "{manifest}");
+        } else {
+            assert!(!ktx2.exists());
+            assert!(manifest.contains("\"textures/icon.png\""), "{manifest}");
+        }
+    }
+
+    #[test]
+    fn web_keeps_textures_as_they_are() {
+        let fixture = Fixture::new("web");
+        let manifest = fixture.process(true);
+        assert!(!fixture.0.join("out/textures/icon.ktx2").exists());
+        assert!(manifest.contains("\"textures/icon.png\""), "{manifest}");
+    }
+}
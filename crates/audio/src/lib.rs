@@ -3,16 +3,20 @@
 //! [Music sampler pool](Music)
 //! [Sfx sampler pool](Sfx)
 //!
+//! [Spatial music sampler pool](SpatialMusic)
+//! [Spatial sfx sampler pool](SpatialSfx)
+//! [Ambient sampler pool](Ambient)
+//!
 //! ```text
-//! ┌─────┐┌───┐┌───────────┐
-//! │Music││Sfx││DefaultPool│
-//! └┬────┘└┬──┘└┬──────────┘
-//! ┌▽──────▽────▽┐
-//! │MainBus      │
-//! └─────────────┘
+//! ┌─────┐┌───┐┌────────────┐┌──────────┐┌───────┐┌───────────┐
+//! │Music││Sfx││SpatialMusic││SpatialSfx││Ambient││DefaultPool│
+//! └┬────┘└┬──┘└┬───────────┘└┬─────────┘└┬──────┘└┬──────────┘
+//! ┌▽──────▽────▽─────────────▽───────────▽────────▽┐
+//! │MainBus                                         │
+//! └────────────────────────────────────────────────┘
 //! ```
 //!
-//! The `Music` pool, `Sfx` pool, and `DefaultPool` are all routed to the `MainBus` node.
+//! All pools are routed to the `MainBus` node.
 //! Since each pool has a `VolumeNode`, we can control them all individually. And,
 //! since they're all routed to the `MainBus`, we can also set the volume of all three
 //! at once.
@@ -40,7 +44,7 @@
 //! ```
 //!
 use bevy::prelude::*;
-use bevy_seedling::{pool::SamplerPool, prelude::*};
+use bevy_seedling::{pool::SamplerPool, prelude::*, sample_effects};
 
 pub fn plugin(app: &mut App) {
     #[cfg(target_arch = "wasm32")]
@@ -75,6 +79,28 @@ fn spawn_pools(mut master: Single<&mut VolumeNode, With<MainBus>>, mut cmds: Com
             volume: Volume::Linear(0.5),
         },
     ));
+
+    // Attenuated by distance and panned relative to the closest `SpatialListener3D`
+    cmds.spawn((
+        SamplerPool(SpatialMusic),
+        sample_effects![SpatialBasicNode::default()],
+        VolumeNode {
+            volume: Volume::Linear(0.5),
+        },
+    ));
+    cmds.spawn((
+        SamplerPool(SpatialSfx),
+        sample_effects![SpatialBasicNode::default()],
+        VolumeNode {
+            volume: Volume::Linear(0.5),
+        },
+    ));
+    cmds.spawn((
+        SamplerPool(Ambient),
+        VolumeNode {
+            volume: Volume::Linear(0.5),
+        },
+    ));
 }
 
 /// An organizational marker component that indicates that [`SamplePlayer`] should be routed to the music sampler pool.
@@ -120,3 +146,41 @@ pub struct Music;
 #[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash, Default, Reflect)]
 #[reflect(Component)]
 pub struct Sfx;
+
+/// Like [`Music`], but played from the position of the [`SamplePlayer`] entity, e.g. a boombox in the scene
+///
+/// ```rust,no_run
+/// commands.spawn((
+///        SpatialMusic,
+///        SamplePlayer::new(handle).looping(),
+///        Transform::from_translation(position),
+///    ));
+/// ```
+#[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash, Default, Reflect)]
+#[reflect(Component)]
+pub struct SpatialMusic;
+
+/// Like [`Sfx`], but played from the position of the [`SamplePlayer`] entity, e.g. footsteps
+///
+/// ```rust,no_run
+/// commands.spawn((
+///        SpatialSfx,
+///        SamplePlayer::new(handle),
+///        Transform::from_translation(position),
+///    ));
+/// ```
+#[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash, Default, Reflect)]
+#[reflect(Component)]
+pub struct SpatialSfx;
+
+/// Looping background sounds of the environment, e.g. rain. Its pool volume fades them in and out
+///
+/// ```rust,no_run
+/// commands.spawn((
+///        Ambient,
+///        SamplePlayer::new(handle).looping(),
+///    ));
+/// ```
+#[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash, Default, Reflect)]
+#[reflect(Component)]
+pub struct Ambient;
@@ -1,17 +1,49 @@
 use super::*;
+use bevy::{
+    asset::RenderAssetUsages,
+    render::{
+        camera::{RenderTarget, Viewport},
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+        view::RenderLayers,
+    },
+    window::{PrimaryWindow, WindowResized},
+};
+use bevy_enhanced_input::prelude::Actions;
 use bevy_third_person_camera::*;
 
+/// Layer of the sprite showing the scaled scene, hidden from the scene cameras
+const PRESENT_LAYER: usize = 1;
+/// Above the co-op cameras, ordered by their slot
+const UI_CAMERA_ORDER: isize = 10;
+
 pub fn plugin(app: &mut App) {
     app.add_systems(Startup, spawn_camera)
         .add_systems(OnEnter(Screen::Title), add_skybox_to_camera)
-        .add_systems(OnEnter(Screen::Gameplay), add_tpv_cam)
+        .add_systems(
+            OnEnter(Screen::Gameplay),
+            add_tpv_cam.after(player::spawn_player),
+        )
         .add_systems(OnExit(Screen::Gameplay), rm_tpv_cam)
+        .add_systems(
+            Update,
+            (
+                (
+                    apply_render_scale
+                        .run_if(resource_changed::<Settings>.or(on_event::<WindowResized>)),
+                    set_camera_viewports.run_if(in_state(Screen::Gameplay)),
+                    assign_ui_camera,
+                )
+                    .chain(),
+                turn_with_stick.run_if(in_state(Screen::Gameplay)),
+            ),
+        )
         .add_observer(toggle_cam_cursor);
 }
 
 pub fn spawn_camera(mut commands: Commands) {
     commands.spawn((
         SceneCamera,
+        MainCamera,
         Camera3d::default(),
         Msaa::Sample4,
         IsDefaultUiCamera,
@@ -23,55 +55,361 @@ pub fn spawn_camera(mut commands: Commands) {
     ));
 }
 
+/// Attaches a third person camera to every player.
+/// The first player reuses the main [`SceneCamera`], co-op players get their own
+/// cameras rendered into split screen viewports.
 fn add_tpv_cam(
     cfg: Res<Config>,
+    settings: Res<Settings>,
     mut commands: Commands,
-    mut camera: Query<Entity, With<SceneCamera>>,
-    mut scene_cam: Query<Entity, With<ThirdPersonCamera>>,
+    camera: Query<Entity, With<MainCamera>>,
+    players: Query<(Entity, &GameplayCtx), With<Player>>,
 ) -> Result {
-    let camera = camera.single_mut()?;
-    if scene_cam.single_mut().is_ok() {
-        debug!("ThirdPersonCamera already exist");
-        return Ok(());
-    }
-
-    commands.entity(camera).insert((
-        ThirdPersonCamera {
-            // aim_speed: 3.0,
-            // aim_zoom: 0.7,
-            // aim_enabled: true,
-            zoom_enabled: true,
-            zoom: Zoom::new(cfg.player.zoom.0, cfg.player.zoom.1),
-            offset_enabled: true,
-            offset_toggle_enabled: true,
-            cursor_lock_key: KeyCode::KeyL,
-            gamepad_settings: CustomGamepadSettings::default(),
-            // bounds: vec![Bound::NO_FLIP, Bound::ABOVE_FLOOR],
-            ..default()
-        },
-        RigidBody::Kinematic,
-        Collider::sphere(1.0),
-        Projection::from(PerspectiveProjection {
-            fov: cfg.player.fov.to_radians(),
-            ..Default::default()
-        }),
-    ));
+    let main_camera = camera.single()?;
+
+    for (player, ctx) in players.iter() {
+        let camera = if ctx.slot == 0 {
+            main_camera
+        } else {
+            let mut camera = commands.spawn((
+                StateScoped(Screen::Gameplay),
+                Name::new(format!("Player {} Camera", ctx.slot + 1)),
+                SceneCamera,
+                Camera3d::default(),
+                Msaa::Sample4,
+                Camera {
+                    hdr: true,
+                    // render after the main camera so viewports don't fight over clear color
+                    order: ctx.slot as isize,
+                    ..Default::default()
+                },
+                atmosphere(&settings.graphics),
+            ));
+            if cfg.physics.distance_fog && settings.graphics.fog {
+                camera.insert(distance_fog(&cfg));
+            }
+            camera.id()
+        };
+
+        commands.entity(camera).insert((
+            ThirdPersonCamera {
+                // aim_speed: 3.0,
+                // aim_zoom: 0.7,
+                // aim_enabled: true,
+                zoom_enabled: true,
+                zoom: Zoom::new(cfg.player.camera.zoom.0, cfg.player.camera.zoom.1),
+                offset_enabled: true,
+                offset_toggle_enabled: true,
+                cursor_lock_key: KeyCode::KeyL,
+                // the crate only reads the right stick of a single gamepad, see `turn_with_stick`
+                gamepad_settings: CustomGamepadSettings {
+                    x_sensitivity: 0.0,
+                    y_sensitivity: 0.0,
+                    ..default()
+                },
+                // bounds: vec![Bound::NO_FLIP, Bound::ABOVE_FLOOR],
+                ..default()
+            },
+            RigidBody::Kinematic,
+            Collider::sphere(1.0),
+            CollisionLayers::new(GameLayer::Camera, LayerMask::ALL),
+            Projection::from(PerspectiveProjection {
+                fov: cfg.player.fov.to_radians(),
+                ..Default::default()
+            }),
+        ));
+        commands.entity(player).insert(PlayerCamera(camera));
+    }
 
     Ok(())
 }
 
-fn rm_tpv_cam(mut commands: Commands, mut camera: Query<Entity, With<SceneCamera>>) {
-    if let Ok(camera) = camera.single_mut() {
+fn rm_tpv_cam(mut commands: Commands, mut camera: Query<(Entity, &mut Camera), With<MainCamera>>) {
+    if let Ok((entity, mut camera)) = camera.single_mut() {
+        camera.viewport = None;
         commands
-            .entity(camera)
+            .entity(entity)
             .remove::<RigidBody>()
             .remove::<ThirdPersonCamera>();
     }
 }
 
-fn toggle_cam_cursor(_: Trigger<OnCamCursorToggle>, mut cam: Query<&mut ThirdPersonCamera>) {
-    let Ok(mut cam) = cam.single_mut() else {
+/// Turns every player camera with the [`Rotate`] action of its player, which reads the camera
+/// stick of the player's own gamepad with the deadzone, sensitivity and invert-Y of the
+/// gamepad settings. [`ThirdPersonCamera`] keeps placing the camera from its rotation.
+fn turn_with_stick(
+    players: Query<(&PlayerCamera, &Actions<GameplayCtx>), With<Player>>,
+    mut cameras: Query<&mut Transform, (With<ThirdPersonCamera>, Without<Player>)>,
+) {
+    for (player_cam, actions) in players.iter() {
+        let Ok(mut transform) = cameras.get_mut(player_cam.0) else {
+            continue;
+        };
+        let turn = actions
+            .value::<Rotate>()
+            .map(|value| value.as_axis2d())
+            .unwrap_or_default();
+        if turn == Vec2::ZERO {
+            continue;
+        }
+        // yaw around the world up, pitch around the camera's own right
+        let yaw = Quat::from_rotation_y(turn.x.to_radians());
+        let pitch = Quat::from_rotation_x(turn.y.to_radians());
+        transform.rotation = yaw * transform.rotation;
+        let tilted = transform.rotation * pitch;
+        // like the mouse orbit of the crate, never tilt over the top
+        if (tilted * Vec3::Y).y > 0.0 {
+            transform.rotation = tilted;
+        }
+    }
+}
+
+/// Keeps split screen viewports in sync with the window size,
+/// or the size of the scaled target the main camera renders into
+fn set_camera_viewports(
+    lobby: Res<Lobby>,
+    settings: Res<Settings>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    players: Query<(&GameplayCtx, &PlayerCamera)>,
+    mut cameras: Query<&mut Camera>,
+) {
+    let total = lobby.players() as u32;
+    let window_size = window.physical_size();
+    for (ctx, player_cam) in &players {
+        let Ok(mut camera) = cameras.get_mut(player_cam.0) else {
+            continue;
+        };
+        let target_size = match camera.target {
+            RenderTarget::Image(_) => scaled_size(window_size, settings.graphics.render_scale),
+            _ => window_size,
+        };
+        let viewport = split_screen_viewport(ctx.slot as u32, total, target_size);
+        let unchanged = match (&camera.viewport, &viewport) {
+            (None, None) => true,
+            (Some(old), Some(new)) => {
+                old.physical_position == new.physical_position
+                    && old.physical_size == new.physical_size
+            }
+            _ => false,
+        };
+        if !unchanged {
+            camera.viewport = viewport;
+        }
+    }
+}
+
+/// One player takes the whole window, two are side by side, three and four share a 2x2 grid
+fn split_screen_viewport(slot: u32, total: u32, window_size: UVec2) -> Option<Viewport> {
+    if total <= 1 {
+        return None;
+    }
+    let grid = if total == 2 {
+        UVec2::new(2, 1)
+    } else {
+        UVec2::new(2, 2)
+    };
+    let size = window_size / grid;
+    // minimized window
+    if size.x == 0 || size.y == 0 {
+        return None;
+    }
+
+    Some(Viewport {
+        physical_position: UVec2::new(slot % grid.x, slot / grid.x) * size,
+        physical_size: size,
+        ..default()
+    })
+}
+
+/// Draws the UI over the whole window while the main camera only renders a split screen viewport
+#[derive(Component)]
+struct UiCamera;
+
+/// Keeps the UI on a camera covering the whole window and drawing last: a camera of its own
+/// in split screen, the present camera while the scene is scaled, otherwise the main camera
+fn assign_ui_camera(
+    main: Single<(Entity, &Camera), With<MainCamera>>,
+    present: Query<Entity, With<PresentCamera>>,
+    ui_cameras: Query<Entity, With<UiCamera>>,
+    owners: Query<Entity, With<IsDefaultUiCamera>>,
+    mut commands: Commands,
+) {
+    let (main, camera) = *main;
+    let split = camera.viewport.is_some();
+    let owner = match (split, ui_cameras.iter().next(), present.iter().next()) {
+        (true, Some(ui_camera), _) => ui_camera,
+        (true, None, _) => commands
+            .spawn((
+                Name::new("UI Camera"),
+                UiCamera,
+                Camera2d,
+                Camera {
+                    // over every split screen viewport, keeping what they drew
+                    order: UI_CAMERA_ORDER,
+                    clear_color: ClearColorConfig::None,
+                    ..default()
+                },
+                Msaa::Off,
+                RenderLayers::none(),
+            ))
+            .id(),
+        (false, _, Some(present)) => present,
+        (false, _, None) => main,
+    };
+
+    if !owners.contains(owner) {
+        commands.entity(owner).insert(IsDefaultUiCamera);
+    }
+    for entity in owners.iter().filter(|entity| *entity != owner) {
+        commands.entity(entity).remove::<IsDefaultUiCamera>();
+    }
+    for entity in ui_cameras.iter().filter(|entity| *entity != owner) {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Offscreen image the main camera renders into below a render scale of 1
+#[derive(Resource)]
+struct ScaledTarget(Handle<Image>);
+
+/// Stretches the [`ScaledTarget`] over the window and draws the UI while it exists
+#[derive(Component)]
+struct PresentCamera;
+
+#[derive(Component)]
+struct ScaledScene;
+
+fn scaled_size(window_size: UVec2, scale: f32) -> UVec2 {
+    (window_size.as_vec2() * scale.min(1.0))
+        .as_uvec2()
+        .max(UVec2::ONE)
+}
+
+/// The main camera renders at a fraction of the window resolution into an image,
+/// which a 2d camera draws over the whole window. The UI moves to that camera to stay sharp.
+fn apply_render_scale(
+    settings: Res<Settings>,
+    target: Option<Res<ScaledTarget>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut images: ResMut<Assets<Image>>,
+    mut camera: Single<&mut Camera, With<MainCamera>>,
+    mut sprites: Query<&mut Sprite, With<ScaledScene>>,
+    presented: Query<Entity, Or<(With<PresentCamera>, With<ScaledScene>)>>,
+    mut commands: Commands,
+) {
+    let scale = settings.graphics.render_scale;
+    if scale >= 1.0 {
+        if target.is_some() {
+            camera.target = RenderTarget::default();
+            camera.order = 0;
+            for entity in presented.iter() {
+                commands.entity(entity).despawn();
+            }
+            commands.remove_resource::<ScaledTarget>();
+        }
         return;
+    }
+
+    let size = scaled_size(window.physical_size(), scale);
+    let extent = Extent3d {
+        width: size.x,
+        height: size.y,
+        depth_or_array_layers: 1,
+    };
+    let handle = match target {
+        Some(target) => {
+            if let Some(image) = images.get_mut(&target.0) {
+                if image.size() != size {
+                    image.resize(extent);
+                }
+            }
+            target.0.clone()
+        }
+        None => {
+            let mut image = Image::new_fill(
+                extent,
+                TextureDimension::D2,
+                &[0, 0, 0, 0],
+                TextureFormat::Bgra8UnormSrgb,
+                RenderAssetUsages::default(),
+            );
+            image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT;
+            let handle = images.add(image);
+            commands.insert_resource(ScaledTarget(handle.clone()));
+            commands.spawn((
+                Name::new("Present Camera"),
+                PresentCamera,
+                Camera2d,
+                Msaa::Off,
+                RenderLayers::layer(PRESENT_LAYER),
+            ));
+            commands.spawn((
+                Name::new("Scaled Scene"),
+                ScaledScene,
+                Sprite {
+                    image: handle.clone(),
+                    custom_size: Some(window.size()),
+                    ..default()
+                },
+                RenderLayers::layer(PRESENT_LAYER),
+            ));
+            handle
+        }
     };
-    cam.cursor_lock_active = !cam.cursor_lock_active;
+
+    // co-op cameras draw their viewports over the presented scene
+    camera.order = -1;
+    camera.target = handle.into();
+    for mut sprite in sprites.iter_mut() {
+        sprite.custom_size = Some(window.size());
+    }
+}
+
+fn toggle_cam_cursor(_: Trigger<OnCamCursorToggle>, mut cams: Query<&mut ThirdPersonCamera>) {
+    for mut cam in cams.iter_mut() {
+        cam.cursor_lock_active = !cam.cursor_lock_active;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: UVec2 = UVec2::new(1920, 1080);
+
+    fn rect(slot: u32, total: u32) -> (UVec2, UVec2) {
+        let viewport = split_screen_viewport(slot, total, WINDOW).unwrap();
+        (viewport.physical_position, viewport.physical_size)
+    }
+
+    #[test]
+    fn single_player_uses_the_whole_window() {
+        assert!(split_screen_viewport(0, 1, WINDOW).is_none());
+    }
+
+    #[test]
+    fn two_players_side_by_side() {
+        let size = UVec2::new(960, 1080);
+        assert_eq!(rect(0, 2), (UVec2::ZERO, size));
+        assert_eq!(rect(1, 2), (UVec2::new(960, 0), size));
+    }
+
+    #[test]
+    fn three_and_four_players_in_a_grid() {
+        let size = UVec2::new(960, 540);
+        for total in [3, 4] {
+            assert_eq!(rect(0, total), (UVec2::ZERO, size));
+            assert_eq!(rect(1, total), (UVec2::new(960, 0), size));
+            assert_eq!(rect(2, total), (UVec2::new(0, 540), size));
+        }
+        assert_eq!(rect(3, 4), (UVec2::new(960, 540), size));
+    }
+
+    #[test]
+    fn minimized_window_has_no_viewport() {
+        assert!(split_screen_viewport(0, 2, UVec2::ZERO).is_none());
+        assert!(split_screen_viewport(1, 4, UVec2::new(1, 1)).is_none());
+    }
 }
@@ -0,0 +1,251 @@
+//! Keeps third person cameras out of walls and the player in sight.
+//!
+//! The camera crate places the camera at the zoom distance every frame, afterwards a sphere cast
+//! from the player shortens the boom to the first static obstacle on
+//! `player.camera.collision_layers`.
+//! It snaps in right away and grows back at `recovery_speed`, meshes still in the way can fade out.
+use super::*;
+use bevy::transform::TransformSystem;
+use bevy_third_person_camera::CameraSyncSet;
+
+/// Alpha change per second of fading meshes
+const FADE_SPEED: f32 = 4.0;
+/// Occluders found per camera, further ones stay opaque
+const MAX_OCCLUDERS: u32 = 8;
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        PostUpdate,
+        (shorten_boom, fade_occluders)
+            .chain()
+            .run_if(in_state(Screen::Gameplay))
+            .after(CameraSyncSet)
+            .before(TransformSystem::TransformPropagate),
+    )
+    .add_systems(OnExit(Screen::Gameplay), restore_faded);
+}
+
+/// Boom length after the last collision, while it recovers
+#[derive(Component)]
+struct Boom(f32);
+
+/// Mesh wearing a faded copy of its material
+#[derive(Component)]
+struct Faded {
+    original: Handle<StandardMaterial>,
+    alpha: f32,
+    /// Still between a camera and its player this frame
+    occluding: bool,
+}
+
+fn collision_filter(
+    cfg: &Config,
+    excluded: impl IntoIterator<Item = Entity>,
+) -> SpatialQueryFilter {
+    let mask = cfg
+        .player
+        .camera
+        .collision_layers
+        .iter()
+        .fold(LayerMask::NONE, |mask, layer| {
+            mask | LayerMask::from(*layer)
+        });
+    SpatialQueryFilter::from_mask(mask).with_excluded_entities(excluded)
+}
+
+/// Only static geometry blocks the view. Triggers like weather zones and bodies that move,
+/// other players, their cameras or props, don't.
+fn blocks_view(
+    entity: Entity,
+    sensors: &Query<(), With<Sensor>>,
+    colliders: &Query<&ColliderOf>,
+    bodies: &Query<&RigidBody>,
+) -> bool {
+    if sensors.contains(entity) {
+        return false;
+    }
+    let body = colliders
+        .get(entity)
+        .map(|collider| collider.body)
+        .unwrap_or(entity);
+    match bodies.get(body) {
+        Ok(body) => body.is_static(),
+        // colliders without a body are static
+        Err(_) => true,
+    }
+}
+
+fn shorten_boom(
+    cfg: Res<Config>,
+    time: Res<Time>,
+    spatial: SpatialQuery,
+    players: Query<(Entity, &Transform, &PlayerCamera), With<Player>>,
+    mut cameras: Query<(&mut Transform, Option<&mut Boom>), Without<Player>>,
+    sensors: Query<(), With<Sensor>>,
+    colliders: Query<&ColliderOf>,
+    bodies: Query<&RigidBody>,
+    mut commands: Commands,
+) {
+    let camera_cfg = &cfg.player.camera;
+    let probe = Collider::sphere(camera_cfg.probe_radius);
+    for (player, player_transform, player_cam) in players.iter() {
+        let Ok((mut transform, boom)) = cameras.get_mut(player_cam.0) else {
+            continue;
+        };
+        let focus = player_transform.translation;
+        let offset = transform.translation - focus;
+        let Ok(direction) = Dir3::new(offset) else {
+            continue;
+        };
+        let wanted = offset.length();
+        let filter = collision_filter(&cfg, [player, player_cam.0]);
+        let free = spatial
+            .cast_shape_predicate(
+                &probe,
+                focus,
+                Quat::IDENTITY,
+                direction,
+                &ShapeCastConfig::from_max_distance(wanted),
+                &filter,
+                &|entity| blocks_view(entity, &sensors, &colliders, &bodies),
+            )
+            .map(|hit| hit.distance)
+            .unwrap_or(wanted);
+
+        let length = match boom {
+            Some(mut boom) => {
+                boom.0 = if free < boom.0 {
+                    free
+                } else {
+                    (boom.0 + camera_cfg.recovery_speed * time.delta_secs()).min(free)
+                };
+                boom.0
+            }
+            None => {
+                commands.entity(player_cam.0).insert(Boom(free));
+                free
+            }
+        };
+        transform.translation = focus + direction * length;
+    }
+}
+
+fn fade_occluders(
+    cfg: Res<Config>,
+    time: Res<Time>,
+    spatial: SpatialQuery,
+    players: Query<(Entity, &Transform, &PlayerCamera), With<Player>>,
+    cameras: Query<&Transform, Without<Player>>,
+    sensors: Query<(), With<Sensor>>,
+    colliders: Query<&ColliderOf>,
+    bodies: Query<&RigidBody>,
+    children: Query<&Children>,
+    mut meshes: Query<(
+        Entity,
+        &mut MeshMaterial3d<StandardMaterial>,
+        Option<&mut Faded>,
+    )>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let camera_cfg = &cfg.player.camera;
+    for (_, _, faded) in meshes.iter_mut() {
+        if let Some(mut faded) = faded {
+            faded.occluding = false;
+        }
+    }
+    // split screen cameras can see the same mesh, it only gets one copy
+    let mut fading = vec![];
+
+    if camera_cfg.fade_occluders {
+        for (player, player_transform, player_cam) in players.iter() {
+            let Ok(camera) = cameras.get(player_cam.0) else {
+                continue;
+            };
+            let offset = player_transform.translation - camera.translation;
+            let Ok(direction) = Dir3::new(offset) else {
+                continue;
+            };
+            let filter = collision_filter(&cfg, [player, player_cam.0]);
+            let hits = spatial.ray_hits(
+                camera.translation,
+                direction,
+                offset.length(),
+                MAX_OCCLUDERS,
+                true,
+                &filter,
+            );
+            for hit in hits
+                .iter()
+                .filter(|hit| blocks_view(hit.entity, &sensors, &colliders, &bodies))
+            {
+                // the collider may sit on a parent of the meshes it was made from
+                let occluder =
+                    std::iter::once(hit.entity).chain(children.iter_descendants(hit.entity));
+                for entity in occluder {
+                    let Ok((_, mut material, faded)) = meshes.get_mut(entity) else {
+                        continue;
+                    };
+                    if let Some(mut faded) = faded {
+                        faded.occluding = true;
+                        continue;
+                    }
+                    if fading.contains(&entity) {
+                        continue;
+                    }
+                    let Some(original) = materials.get(&material.0) else {
+                        continue;
+                    };
+                    // a copy per mesh, other meshes may share the material
+                    let copy = StandardMaterial {
+                        alpha_mode: AlphaMode::Blend,
+                        ..original.clone()
+                    };
+                    let original = std::mem::replace(&mut material.0, materials.add(copy));
+                    fading.push(entity);
+                    commands.entity(entity).insert(Faded {
+                        original,
+                        alpha: 1.0,
+                        occluding: true,
+                    });
+                }
+            }
+        }
+    }
+
+    let step = FADE_SPEED * time.delta_secs();
+    for (entity, mut material, faded) in meshes.iter_mut() {
+        let Some(mut faded) = faded else {
+            continue;
+        };
+        let target = if faded.occluding {
+            camera_cfg.fade_alpha
+        } else {
+            1.0
+        };
+        faded.alpha += (target - faded.alpha).clamp(-step, step);
+        if !faded.occluding && faded.alpha >= 1.0 {
+            material.0 = faded.original.clone();
+            commands.entity(entity).remove::<Faded>();
+            continue;
+        }
+        let original_alpha = materials
+            .get(&faded.original)
+            .map(|m| m.base_color.alpha())
+            .unwrap_or(1.0);
+        if let Some(copy) = materials.get_mut(&material.0) {
+            copy.base_color.set_alpha(original_alpha * faded.alpha);
+        }
+    }
+}
+
+/// Levels can outlive gameplay, put the original materials back
+fn restore_faded(
+    mut faded: Query<(Entity, &mut MeshMaterial3d<StandardMaterial>, &Faded)>,
+    mut commands: Commands,
+) {
+    for (entity, mut material, faded) in faded.iter_mut() {
+        material.0 = faded.original.clone();
+        commands.entity(entity).remove::<Faded>();
+    }
+}
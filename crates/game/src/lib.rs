@@ -7,24 +7,26 @@ use models::*;
 use scene::*;
 
 mod camera;
+mod camera_collision;
 #[cfg(feature = "dev_native")]
 mod dev_tools;
+mod save;
+mod score;
 mod sound;
 
 pub use camera::*;
 
 pub fn plugin(app: &mut App) {
-    app.insert_resource(Score(0));
     app.add_plugins((
         models::plugin,
         camera::plugin,
+        camera_collision::plugin,
         scene::plugin,
         player::plugin,
+        save::plugin,
+        score::plugin,
         sound::plugin,
         #[cfg(feature = "dev_native")]
         dev_tools::plugin,
     ));
 }
-
-#[derive(Default, Resource)]
-pub struct Score(pub i32);
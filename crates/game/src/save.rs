@@ -0,0 +1,74 @@
+use super::*;
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        apply_loaded_save
+            .after(player::spawn_player)
+            .after(score::respawn_collectibles)
+            .after(scene::start_day)
+            .run_if(resource_exists::<LoadedSave>),
+    )
+    .add_observer(save_game);
+}
+
+fn save_game(
+    on: Trigger<OnSaveGame>,
+    state: Res<GameState>,
+    players: Query<(&GameplayCtx, &Transform, &Health), With<Player>>,
+    time_of_day: Res<TimeOfDay>,
+    collected: Query<&Name, With<Collected>>,
+) {
+    let slot = **on.event();
+    let mut players = players.iter().collect::<Vec<_>>();
+    players.sort_by_key(|(ctx, ..)| ctx.slot);
+
+    let mut save = SaveData {
+        players: players
+            .into_iter()
+            .map(|(_, transform, health)| PlayerSave {
+                translation: transform.translation.to_array(),
+                rotation: transform.rotation.to_array(),
+                health: health.current,
+            })
+            .collect(),
+        score: state.score.run,
+        hour: time_of_day.hour,
+        collected: collected.iter().map(|name| name.to_string()).collect(),
+        ..default()
+    };
+
+    match save.write(slot) {
+        Ok(()) => info!("saved game to '{}'", SaveData::path(slot).display()),
+        Err(e) => error!("failed to save '{}': {e}", SaveData::path(slot).display()),
+    }
+}
+
+fn apply_loaded_save(
+    save: Res<LoadedSave>,
+    mut commands: Commands,
+    mut state: ResMut<GameState>,
+    mut players: Query<(&GameplayCtx, &mut Transform, &mut Health), With<Player>>,
+    mut time_of_day: ResMut<TimeOfDay>,
+    collectibles: Query<(Entity, &Name), With<Collectible>>,
+) {
+    let save = &save.0;
+    for (ctx, mut transform, mut health) in players.iter_mut() {
+        let Some(player) = save.players.get(ctx.slot) else {
+            continue;
+        };
+        transform.translation = Vec3::from_array(player.translation);
+        transform.rotation = Quat::from_array(player.rotation);
+        health.current = player.health;
+    }
+    time_of_day.set(save.hour);
+    for (entity, name) in collectibles.iter() {
+        if save.collected.iter().any(|c| c.as_str() == name.as_str()) {
+            commands
+                .entity(entity)
+                .insert((Collected, ColliderDisabled, Visibility::Hidden));
+        }
+    }
+    state.score.run = save.score;
+    commands.remove_resource::<LoadedSave>();
+}
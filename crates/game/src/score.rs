@@ -0,0 +1,67 @@
+use super::*;
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Gameplay), respawn_collectibles)
+        .add_observer(add_collider)
+        .add_observer(pickup);
+}
+
+/// Collectibles exported without a collider get a sphere one
+fn add_collider(
+    on: Trigger<OnAdd, Collectible>,
+    collectibles: Query<&Collectible, Without<Collider>>,
+    mut commands: Commands,
+) {
+    if let Ok(collectible) = collectibles.get(on.target()) {
+        commands
+            .entity(on.target())
+            .insert(Collider::sphere(collectible.radius));
+    }
+}
+
+fn pickup(
+    on: Trigger<OnCollisionStart>,
+    mut state: ResMut<GameState>,
+    collectibles: Query<&Collectible, Without<Collected>>,
+    players: Query<(), With<Player>>,
+    mut commands: Commands,
+) {
+    let Ok(collectible) = collectibles.get(on.target()) else {
+        return;
+    };
+    let event = on.event();
+    let by_player =
+        players.contains(event.collider) || event.body.is_some_and(|b| players.contains(b));
+    if !by_player {
+        return;
+    }
+
+    commands
+        .entity(on.target())
+        .insert((Collected, ColliderDisabled, Visibility::Hidden));
+    if state.score.add(collectible.value) {
+        if let Err(e) = state.score.save() {
+            error!(
+                "failed to save high score to '{}': {e}",
+                Score::path().display()
+            );
+        }
+    }
+    info!(
+        "score: {}, high score: {}",
+        state.score.run, state.score.high
+    );
+}
+
+/// The scene outlives gameplay, so collectibles picked up in a previous run come back
+pub(crate) fn respawn_collectibles(
+    collected: Query<Entity, With<Collected>>,
+    mut commands: Commands,
+) {
+    for entity in collected.iter() {
+        commands
+            .entity(entity)
+            .remove::<(Collected, ColliderDisabled)>()
+            .insert(Visibility::Inherited);
+    }
+}
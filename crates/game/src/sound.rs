@@ -1,41 +1,316 @@
+//! Music director: picks a track for the current [`Mood`] and crossfades to it
+//! by ramping the `Music` pool volume down and back up.
+//!
+//! Every track keeps its own looping player, paused while another one plays,
+//! so going back to a mood resumes its track where it left off.
+//! Players hitting or getting hit switch gameplay to [`Mood::Combat`] for `music.combat_linger`.
+//!
+//! Cameras listen to spatial audio: the boombox music and player sounds.
+//! Close to a boombox the soundtrack ducks under it.
+//!
+//! The weather plays its ambient loop through the `Ambient` pool.
 use super::*;
+use bevy::platform::collections::HashMap;
 use rand::prelude::*;
 
 pub fn plugin(app: &mut App) {
-    app.add_systems(OnExit(Screen::Gameplay), stop_soundtrack)
-        .add_systems(OnEnter(Screen::Gameplay), start_soundtrack);
+    app.init_resource::<MusicDirector>()
+        .add_systems(Update, screen_mood.run_if(state_changed::<Screen>))
+        .add_systems(OnExit(Screen::Gameplay), leave_zones)
+        .add_systems(Update, cool_down.run_if(in_state(Screen::Gameplay)))
+        .add_systems(
+            Update,
+            (pick_track, duck_near_boombox, crossfade)
+                .chain()
+                .run_if(resource_exists::<Config>),
+        )
+        .add_systems(Update, weather_ambience)
+        .add_systems(
+            Update,
+            spatial_volume.run_if(
+                resource_exists_and_changed::<Settings>
+                    .or(resource_exists_and_changed::<GameState>),
+            ),
+        )
+        .add_observer(fight)
+        .add_observer(enter_zone)
+        .add_observer(exit_zone)
+        .add_observer(add_listener)
+        .add_observer(play_boombox);
 }
 
-// TODO: implement different music states
-// TODO: basic track/mood change per zone
-// good structure in this example: <https://github.com/bevyengine/bevy/blob/main/examples/audio/soundtrack.rs#L29>
-fn start_soundtrack(
-    mut cmds: Commands,
+#[derive(Resource, Default)]
+pub struct MusicDirector {
+    /// Mood of the current screen, no music until there is one
+    pub mood: Option<Mood>,
+    /// Music zones players are in as `(zone, player, mood)`, the latest entered wins
+    zones: Vec<(Entity, Entity, Mood)>,
+    /// Seconds of [`Mood::Combat`] left since a player last hit or got hit
+    fighting: f32,
+    /// Mood the playing track was picked for
+    picked: Option<Mood>,
+    playing: Option<RonHandle<Sample>>,
+    next: Option<RonHandle<Sample>>,
+    /// Crossfade factor of the pool volume
+    fade: f32,
+    /// How far the pool volume is lowered for a nearby [`Boombox`], 1 is silent
+    duck: f32,
+    /// Looping player of every track played so far, by path
+    players: HashMap<String, Entity>,
+}
+
+impl MusicDirector {
+    pub fn target(&self) -> Option<Mood> {
+        // fights win over zones, not over menus
+        if self.fighting > 0.0 && self.mood == Some(Mood::Explore) {
+            return Some(Mood::Combat);
+        }
+        self.zones.last().map(|(_, _, mood)| *mood).or(self.mood)
+    }
+}
+
+fn screen_mood(screen: Res<State<Screen>>, mut director: ResMut<MusicDirector>) {
+    director.mood = match screen.get() {
+        Screen::Splash | Screen::Loading => None,
+        Screen::Credits => Some(Mood::Credits),
+        Screen::Gameplay => Some(Mood::Explore),
+        _ => Some(Mood::Menu),
+    };
+}
+
+fn pick_track(cfg: Res<Config>, mut director: ResMut<MusicDirector>) {
+    let Some(mood) = director.target() else {
+        return;
+    };
+    if director.picked == Some(mood) {
+        return;
+    }
+    director.picked = Some(mood);
+
+    let tracks = cfg.music.tracks(mood);
+    let current = director.next.as_ref().or(director.playing.as_ref());
+    if current.is_some_and(|current| tracks.iter().any(|t| t.path == current.path)) {
+        return;
+    }
+    let Some(track) = tracks.choose(&mut thread_rng()) else {
+        warn!("no music tracks for {mood:?} in config.ron");
+        return;
+    };
+    if director
+        .playing
+        .as_ref()
+        .is_some_and(|p| p.path == track.path)
+    {
+        // faded back before the switch
+        director.next = None;
+    } else {
+        director.next = Some((*track).clone());
+    }
+}
+
+fn crossfade(
+    time: Res<Time<Real>>,
+    cfg: Res<Config>,
     settings: Res<Settings>,
-    sources: ResMut<AudioSources>,
-    // boombox: Query<Entity, With<Boombox>>,
+    state: Res<GameState>,
+    mut director: ResMut<MusicDirector>,
+    mut playback: Query<&mut PlaybackSettings>,
+    mut music: Single<&mut VolumeNode, (With<SamplerPool<Music>>, Without<SamplerPool<Sfx>>)>,
+    mut applied: Local<Option<f32>>,
+    mut commands: Commands,
+) {
+    let step = time.delta_secs() / cfg.music.fade.max(f32::EPSILON);
+    let fade = director.fade;
+    if director.next.is_some() && director.playing.is_some() && fade > 0.0 {
+        director.fade = (fade - step).max(0.0);
+    } else if let Some(next) = director.next.take() {
+        if let Some(mut playing) = director
+            .playing
+            .as_ref()
+            .and_then(|track| director.players.get(&track.path))
+            .and_then(|e| playback.get_mut(*e).ok())
+        {
+            playing.pause();
+        }
+
+        match director.players.get(&next.path) {
+            Some(entity) => {
+                if let Ok(mut paused) = playback.get_mut(*entity) {
+                    paused.play();
+                }
+            }
+            None => {
+                let player = commands
+                    .spawn((
+                        Name::new(format!("Music: {}", next.path)),
+                        Music,
+                        SamplePlayer::new(next.handle.clone()).looping(),
+                    ))
+                    .id();
+                director.players.insert(next.path.clone(), player);
+            }
+        }
+        director.playing = Some(next);
+    } else if fade < 1.0 {
+        director.fade = (fade + step).min(1.0);
+    }
+
+    // the director owns the pool volume, a volume change or unmuting applies the level again
+    if settings.is_changed() || state.is_changed() {
+        *applied = None;
+    }
+    let level = director.fade * (1.0 - director.duck);
+    if *applied != Some(level) {
+        music.volume = if state.muted {
+            Volume::SILENT
+        } else {
+            settings.music_faded(level)
+        };
+        *applied = Some(level);
+    }
+}
+
+/// The boombox plays the soundtrack too, fade it out around one so the two don't overlap
+fn duck_near_boombox(
+    cfg: Res<Config>,
+    listeners: Query<&GlobalTransform, With<SpatialListener3D>>,
+    boomboxes: Query<&GlobalTransform, With<Boombox>>,
+    mut director: ResMut<MusicDirector>,
+) {
+    let nearest = listeners
+        .iter()
+        .flat_map(|listener| {
+            boomboxes
+                .iter()
+                .map(|boombox| listener.translation().distance(boombox.translation()))
+        })
+        .fold(f32::INFINITY, f32::min);
+    let duck = 1.0 - (nearest / cfg.music.boombox_duck.max(f32::EPSILON)).min(1.0);
+    if director.duck != duck {
+        director.duck = duck;
+    }
+}
+
+fn fight(
+    on: Trigger<OnDamage>,
+    cfg: Res<Config>,
+    players: Query<(), With<Player>>,
+    mut director: ResMut<MusicDirector>,
+) {
+    if players.contains(on.target) || players.contains(on.source) {
+        director.fighting = cfg.music.combat_linger;
+    }
+}
+
+fn cool_down(time: Res<Time>, mut director: ResMut<MusicDirector>) {
+    if director.fighting > 0.0 {
+        director.fighting = (director.fighting - time.delta_secs()).max(0.0);
+    }
+}
+
+fn enter_zone(
+    on: Trigger<OnCollisionStart>,
+    zones: Query<&MusicZone>,
+    players: Query<(), With<Player>>,
+    mut director: ResMut<MusicDirector>,
 ) {
-    let mut rng = thread_rng();
-    let handle = *[&sources.bg_music].choose(&mut rng).unwrap();
-
-    // // Play music from boombox entity
-    // cmds
-    //     .entity(boombox.single()?)
-    //     .insert(music(handle.clone(), settings.music());
-    // Or just play music
-    cmds.spawn((
-        Music,
-        SamplePlayer::new(handle.clone())
-            .with_volume(settings.music())
-            .looping(),
+    let Ok(zone) = zones.get(on.target()) else {
+        return;
+    };
+    let event = on.event();
+    let Some(player) = [Some(event.collider), event.body]
+        .into_iter()
+        .flatten()
+        .find(|e| players.contains(*e))
+    else {
+        return;
+    };
+    director.zones.push((on.target(), player, zone.mood));
+}
+
+fn exit_zone(on: Trigger<OnCollisionEnd>, mut director: ResMut<MusicDirector>) {
+    let event = on.event();
+    director.zones.retain(|(zone, player, _)| {
+        *zone != on.target() || (*player != event.collider && Some(*player) != event.body)
+    });
+}
+
+fn leave_zones(mut director: ResMut<MusicDirector>) {
+    director.zones.clear();
+    director.fighting = 0.0;
+}
+
+fn add_listener(on: Trigger<OnAdd, SceneCamera>, mut commands: Commands) {
+    commands.entity(on.target()).insert(SpatialListener3D);
+}
+
+fn play_boombox(on: Trigger<OnAdd, Boombox>, cfg: Option<Res<Config>>, mut commands: Commands) {
+    let Some(cfg) = cfg else {
+        return;
+    };
+    commands.entity(on.target()).with_child((
+        Name::new("Boombox Music"),
+        SpatialMusic,
+        SamplePlayer::new(cfg.music.boombox.handle.clone()).looping(),
+        Transform::default(),
     ));
 }
 
-fn stop_soundtrack(
-    // boombox: Query<Entity, With<Boombox>>,
-    mut bg_music: Query<&mut PlaybackSettings, With<Music>>,
+/// Spatial pools follow the volume settings and mute like the regular ones
+fn spatial_volume(
+    settings: Res<Settings>,
+    state: Res<GameState>,
+    mut music: Single<
+        &mut VolumeNode,
+        (
+            With<SamplerPool<SpatialMusic>>,
+            Without<SamplerPool<SpatialSfx>>,
+        ),
+    >,
+    mut sfx: Single<
+        &mut VolumeNode,
+        (
+            With<SamplerPool<SpatialSfx>>,
+            Without<SamplerPool<SpatialMusic>>,
+        ),
+    >,
+) {
+    if state.muted {
+        music.volume = Volume::SILENT;
+        sfx.volume = Volume::SILENT;
+    } else {
+        music.volume = settings.music();
+        sfx.volume = settings.sfx();
+    }
+}
+
+/// Swaps the loop when the weather's ambient sound changes, the pool volume does the fading
+fn weather_ambience(
+    weather: Res<WeatherState>,
+    settings: Res<Settings>,
+    state: Res<GameState>,
+    server: Res<AssetServer>,
+    mut playing: Local<Option<(String, Entity)>>,
+    mut ambient: Single<&mut VolumeNode, With<SamplerPool<Ambient>>>,
+    mut commands: Commands,
 ) {
-    for mut s in bg_music.iter_mut() {
-        s.pause();
+    let (path, fade) = weather.ambient();
+    if playing.as_ref().map(|(playing, _)| playing.as_str()) != path {
+        if let Some((_, player)) = playing.take() {
+            commands.entity(player).despawn();
+        }
+        *playing = path.map(|path| {
+            let player = commands.spawn((
+                Name::new(format!("Ambient: {path}")),
+                Ambient,
+                SamplePlayer::new(server.load(path)).looping(),
+            ));
+            (path.to_string(), player.id())
+        });
     }
+    ambient.volume = if state.muted {
+        Volume::SILENT
+    } else {
+        settings.sfx_faded(fade)
+    };
 }
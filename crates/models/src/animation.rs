@@ -0,0 +1,189 @@
+use super::*;
+
+/// Maps every [`AnimationState`] to a clip of the character model.
+///
+/// Loaded from `animations.ron`, so swapping the character model only needs a RON edit.
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect, Asset, Resource)]
+#[reflect(Resource)]
+pub struct AnimationSet {
+    pub stand_idle: AnimationEntry,
+    pub run: AnimationEntry,
+    pub sprint: AnimationEntry,
+    pub climb: AnimationEntry,
+    pub jump_start: AnimationEntry,
+    pub jump_loop: AnimationEntry,
+    pub jump_land: AnimationEntry,
+    pub fall: AnimationEntry,
+    pub crawl: AnimationEntry,
+    pub crouch: AnimationEntry,
+    pub dash: AnimationEntry,
+    pub wall_slide: AnimationEntry,
+    pub wall_jump: AnimationEntry,
+    pub knock_back: AnimationEntry,
+    /// Melee combo strikes in order
+    #[serde(default)]
+    pub attacks: Vec<AttackEntry>,
+}
+
+impl AnimationSet {
+    pub fn get(&self, state: &AnimationState) -> &AnimationEntry {
+        match state {
+            AnimationState::StandIdle => &self.stand_idle,
+            AnimationState::Run(_) => &self.run,
+            AnimationState::Sprint(_) => &self.sprint,
+            AnimationState::Climb(_) => &self.climb,
+            AnimationState::JumpStart => &self.jump_start,
+            AnimationState::JumpLoop => &self.jump_loop,
+            AnimationState::JumpLand => &self.jump_land,
+            AnimationState::Fall => &self.fall,
+            AnimationState::Crawl(_) => &self.crawl,
+            AnimationState::Crouch => &self.crouch,
+            AnimationState::Dash => &self.dash,
+            AnimationState::WallSlide => &self.wall_slide,
+            AnimationState::WallJump => &self.wall_jump,
+            AnimationState::KnockBack => &self.knock_back,
+            AnimationState::Attack(step) => self
+                .attacks
+                .get(*step)
+                .map(|attack| &attack.animation)
+                .unwrap_or(&self.stand_idle),
+        }
+    }
+
+    /// Every entry with the name of the state it is used for
+    pub fn entries(&self) -> [(&'static str, &AnimationEntry); 14] {
+        [
+            ("stand_idle", &self.stand_idle),
+            ("run", &self.run),
+            ("sprint", &self.sprint),
+            ("climb", &self.climb),
+            ("jump_start", &self.jump_start),
+            ("jump_loop", &self.jump_loop),
+            ("jump_land", &self.jump_land),
+            ("fall", &self.fall),
+            ("crawl", &self.crawl),
+            ("crouch", &self.crouch),
+            ("dash", &self.dash),
+            ("wall_slide", &self.wall_slide),
+            ("wall_jump", &self.wall_jump),
+            ("knock_back", &self.knock_back),
+        ]
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct AnimationEntry {
+    /// Name of the clip in the gltf file
+    pub clip: String,
+    /// Multiplied by the speed carried by the state, if any
+    pub speed: f32,
+    pub looping: bool,
+    /// Seconds to blend from the previous animation
+    pub blend: f32,
+}
+
+/// A combo strike, the hitbox is live between the `hit` timestamps of the clip
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
+pub struct AttackEntry {
+    pub animation: AnimationEntry,
+    /// Seconds into the clip when the hitbox turns on and off
+    pub hit: (f32, f32),
+}
+
+impl Default for AnimationEntry {
+    fn default() -> Self {
+        Self {
+            clip: String::new(),
+            speed: 1.0,
+            looping: false,
+            blend: 0.2,
+        }
+    }
+}
+
+/// Locomotion blend node of the player [`AnimationGraph`] with its idle/jog/sprint clips,
+/// weighted against each other by the ground speed.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct Locomotion {
+    pub node: AnimationNodeIndex,
+    pub idle: AnimationNodeIndex,
+    pub jog: AnimationNodeIndex,
+    pub sprint: AnimationNodeIndex,
+}
+
+impl Locomotion {
+    pub fn clips(&self) -> [AnimationNodeIndex; 3] {
+        [self.idle, self.jog, self.sprint]
+    }
+
+    /// 1D blend weights of idle/jog/sprint for the given speed.
+    /// Idle fades into jog up to `run`, jog fades into sprint up to `sprint`.
+    pub fn weights(speed: f32, threshold: f32, run: f32, sprint: f32) -> [f32; 3] {
+        if speed <= threshold {
+            [1.0, 0.0, 0.0]
+        } else if speed <= run {
+            let t = (speed - threshold) / (run - threshold).max(f32::EPSILON);
+            [1.0 - t, t, 0.0]
+        } else {
+            let t = ((speed - run) / (sprint - run).max(f32::EPSILON)).min(1.0);
+            [0.0, 1.0 - t, t]
+        }
+    }
+}
+
+/// Linear crossfade between nodes of the player [`AnimationGraph`].
+/// The target node fades in while every other playing node fades out.
+#[derive(Component, Reflect, Default, Clone, Debug)]
+#[reflect(Component)]
+pub struct Crossfade {
+    pub target: Option<AnimationNodeIndex>,
+    /// Seconds for a full fade
+    pub duration: f32,
+    /// Current weight of each faded node
+    pub weights: Vec<(AnimationNodeIndex, f32)>,
+}
+
+impl Crossfade {
+    /// Starts fading into `node`, returns `false` if it is already the target
+    pub fn fade_to(&mut self, node: AnimationNodeIndex, duration: f32) -> bool {
+        if self.target == Some(node) {
+            return false;
+        }
+        self.target = Some(node);
+        self.duration = duration;
+        if !self.weights.iter().any(|(n, _)| *n == node) {
+            // Nothing to fade from on the very first animation
+            let weight = if self.weights.is_empty() { 1.0 } else { 0.0 };
+            self.weights.push((node, weight));
+        }
+        true
+    }
+
+    /// Moves weights towards the target, returns nodes that faded out completely
+    pub fn advance(&mut self, dt: f32) -> Vec<AnimationNodeIndex> {
+        let step = if self.duration > 0.0 {
+            dt / self.duration
+        } else {
+            1.0
+        };
+        for (node, weight) in self.weights.iter_mut() {
+            *weight = if Some(*node) == self.target {
+                (*weight + step).min(1.0)
+            } else {
+                (*weight - step).max(0.0)
+            };
+        }
+        let target = self.target;
+        let faded = self
+            .weights
+            .iter()
+            .filter(|(node, weight)| *weight <= 0.0 && Some(*node) != target)
+            .map(|(node, _)| *node)
+            .collect();
+        self.weights
+            .retain(|(node, weight)| *weight > 0.0 || Some(*node) == target);
+        faded
+    }
+}
@@ -0,0 +1,28 @@
+use super::*;
+use avian3d::prelude::*;
+
+/// Scene object picked up by walking into it, authored in Blender through Skein.
+///
+/// Without an exported collider it gets a sphere sensor of `radius`.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component, Default)]
+#[require(Sensor, CollisionEventsEnabled)]
+pub struct Collectible {
+    /// Points added to the run [`Score`]
+    pub value: i32,
+    pub radius: f32,
+}
+
+impl Default for Collectible {
+    fn default() -> Self {
+        Self {
+            value: 1,
+            radius: 0.5,
+        }
+    }
+}
+
+/// Collectible picked up in the current run, hidden until the next one
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct Collected;
@@ -0,0 +1,84 @@
+use super::*;
+
+/// Hit points of anything that can be damaged.
+///
+/// Reflected, so it can be put on scene objects through Skein, e.g. training dummies.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self::new(100.0)
+    }
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+
+    /// Returns the damage actually taken
+    pub fn damage(&mut self, amount: f32) -> f32 {
+        let taken = amount.min(self.current);
+        self.current -= taken;
+        taken
+    }
+}
+
+/// Melee combo state of a player.
+///
+/// Pressing attack during a swing queues the next strike, pressing it within
+/// the combo window after the swing ended continues the combo, otherwise it starts over.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct Combo {
+    /// Current strike in the combo
+    pub step: usize,
+    pub attacking: bool,
+    /// Attack was pressed during the swing
+    pub queued: bool,
+    /// Hitbox is live between the hit animation events
+    pub hitbox: bool,
+    /// Entities already hit by the current swing
+    pub hits: Vec<Entity>,
+    /// Time left to continue the combo after a swing
+    pub window: Timer,
+}
+
+impl Combo {
+    /// Starts the next strike out of `len`, returns its index
+    pub fn next(&mut self, len: usize) -> usize {
+        self.step = if self.window.finished() || self.window.duration().is_zero() {
+            0
+        } else {
+            (self.step + 1) % len.max(1)
+        };
+        self.attacking = true;
+        self.queued = false;
+        self.hitbox = false;
+        self.hits.clear();
+        self.window.reset();
+        self.window.pause();
+        self.step
+    }
+
+    /// Swing finished, opens the combo window
+    pub fn end(&mut self, window: f32) {
+        self.attacking = false;
+        self.hitbox = false;
+        self.window = Timer::from_seconds(window, TimerMode::Once);
+    }
+
+    /// Interrupted by a hit, the combo starts over
+    pub fn interrupt(&mut self) {
+        *self = Self::default();
+    }
+}
@@ -0,0 +1,366 @@
+use super::*;
+use bevy::{
+    ecs::schedule::common_conditions::any_match_filter,
+    window::{Monitor, PresentMode, PrimaryWindow, WindowMode},
+};
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        apply_display
+            .run_if(resource_exists_and_changed::<Settings>.or(any_match_filter::<Added<Monitor>>)),
+    );
+    #[cfg(not(target_arch = "wasm32"))]
+    app.add_systems(Last, limit_frame_rate);
+}
+
+#[derive(Reflect, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenMode {
+    #[default]
+    Windowed,
+    /// Fullscreen window at the desktop resolution
+    Borderless,
+    /// Exclusive fullscreen, switches the monitor to the chosen resolution
+    Fullscreen,
+}
+
+impl ScreenMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScreenMode::Windowed => "Windowed",
+            ScreenMode::Borderless => "Borderless",
+            ScreenMode::Fullscreen => "Fullscreen",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ScreenMode::Windowed => ScreenMode::Borderless,
+            ScreenMode::Borderless => ScreenMode::Fullscreen,
+            ScreenMode::Fullscreen => ScreenMode::Windowed,
+        }
+    }
+}
+
+/// Window options, read from `settings.ron` before the window is created and applied live after
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct DisplaySettings {
+    pub mode: ScreenMode,
+    /// Physical pixels of the window or the fullscreen video mode,
+    /// `None` keeps the default window size and the desktop resolution
+    pub resolution: Option<(u32, u32)>,
+    /// Index in the order the platform lists monitors
+    pub monitor: usize,
+    pub vsync: bool,
+    /// Frames per second, 0 is unlimited
+    pub fps_cap: u32,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            mode: ScreenMode::Windowed,
+            resolution: None,
+            monitor: 0,
+            vsync: true,
+            fps_cap: 0,
+        }
+    }
+}
+
+impl DisplaySettings {
+    pub const FPS_CAPS: [u32; 6] = [0, 30, 60, 120, 144, 240];
+
+    /// Sets the mode, size, position and present mode of `window`.
+    /// Without the selected `monitor`, exclusive fullscreen keeps the current video mode.
+    pub fn apply(&self, window: &mut Window, monitor: Option<&Monitor>) {
+        let selection = MonitorSelection::Index(self.monitor);
+        window.present_mode = self.present_mode();
+        window.mode = match self.mode {
+            ScreenMode::Windowed => WindowMode::Windowed,
+            ScreenMode::Borderless => WindowMode::BorderlessFullscreen(selection),
+            ScreenMode::Fullscreen => WindowMode::Fullscreen(selection, self.video_mode(monitor)),
+        };
+        if self.mode == ScreenMode::Windowed {
+            if let Some((width, height)) = self.resolution {
+                window.resolution.set_physical_resolution(width, height);
+            }
+            window.position = WindowPosition::Centered(selection);
+        }
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        if self.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        }
+    }
+
+    /// Mode, resolution and monitor, the options that need to be confirmed after a change
+    pub fn same_window(&self, other: &Self) -> bool {
+        self.mode == other.mode
+            && self.resolution == other.resolution
+            && self.monitor == other.monitor
+    }
+
+    /// Highest refresh rate video mode of the resolution
+    fn video_mode(&self, monitor: Option<&Monitor>) -> VideoModeSelection {
+        let (Some(monitor), Some((width, height))) = (monitor, self.resolution) else {
+            return VideoModeSelection::Current;
+        };
+        monitor
+            .video_modes
+            .iter()
+            .filter(|mode| mode.physical_size == UVec2::new(width, height))
+            .max_by_key(|mode| (mode.refresh_rate_millihertz, mode.bit_depth))
+            .map(|mode| VideoModeSelection::Specific(*mode))
+            .unwrap_or(VideoModeSelection::Current)
+    }
+
+    /// Steps a resolution or the frame rate cap by `step`, cycles everything else.
+    /// `monitors` are in platform order, see [`monitor_list`].
+    pub fn change(&mut self, option: DisplayOption, step: i32, monitors: &[&Monitor]) {
+        match option {
+            DisplayOption::Mode => self.mode = self.mode.next(),
+            DisplayOption::Monitor => {
+                self.monitor = (self.monitor + 1) % monitors.len().max(1);
+                // the other monitor may not support it
+                self.resolution = None;
+            }
+            DisplayOption::Resolution => {
+                let Some(monitor) = monitors.get(self.monitor) else {
+                    return;
+                };
+                let sizes = resolutions(monitor);
+                if sizes.is_empty() {
+                    return;
+                }
+                // the desktop resolution counts as the largest one
+                let current = self
+                    .resolution
+                    .and_then(|size| sizes.iter().position(|s| *s == size))
+                    .unwrap_or(sizes.len() - 1);
+                let next = (current as i32 + step).clamp(0, sizes.len() as i32 - 1);
+                self.resolution = Some(sizes[next as usize]);
+            }
+            DisplayOption::Vsync => self.vsync = !self.vsync,
+            DisplayOption::FpsCap => {
+                let current = Self::FPS_CAPS.iter().position(|cap| *cap == self.fps_cap);
+                let next =
+                    (current.unwrap_or(0) as i32 + step).clamp(0, Self::FPS_CAPS.len() as i32 - 1);
+                self.fps_cap = Self::FPS_CAPS[next as usize];
+            }
+        }
+    }
+
+    /// Falls back to the first monitor and the desktop resolution when the saved ones
+    /// went away, e.g. a monitor was unplugged. Returns whether anything was reset.
+    pub fn clamp(&mut self, monitors: &[&Monitor]) -> bool {
+        if monitors.is_empty() {
+            return false;
+        }
+        let mut reset = false;
+        if self.monitor >= monitors.len() {
+            self.monitor = 0;
+            self.resolution = None;
+            reset = true;
+        }
+        if let Some(size) = self.resolution {
+            if !resolutions(monitors[self.monitor]).contains(&size) {
+                self.resolution = None;
+                reset = true;
+            }
+        }
+        reset
+    }
+}
+
+/// Unique sizes of the monitor video modes, smallest first
+pub fn resolutions(monitor: &Monitor) -> Vec<(u32, u32)> {
+    let mut sizes: Vec<_> = monitor
+        .video_modes
+        .iter()
+        .map(|mode| (mode.physical_size.x, mode.physical_size.y))
+        .collect();
+    sizes.sort_by_key(|(width, height)| width * height);
+    sizes.dedup();
+    sizes
+}
+
+/// Monitors in the order [`MonitorSelection::Index`] counts them, they are spawned in that order
+pub fn monitor_list<'a>(monitors: impl Iterator<Item = (Entity, &'a Monitor)>) -> Vec<&'a Monitor> {
+    let mut monitors: Vec<_> = monitors.collect();
+    monitors.sort_by_key(|(entity, _)| *entity);
+    monitors.into_iter().map(|(_, monitor)| monitor).collect()
+}
+
+/// Settings row of a [`DisplaySettings`] option, its label is refreshed when settings change
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+pub enum DisplayOption {
+    Mode,
+    Monitor,
+    Resolution,
+    Vsync,
+    FpsCap,
+}
+
+impl DisplayOption {
+    pub fn value(&self, display: &DisplaySettings) -> String {
+        match self {
+            DisplayOption::Mode => display.mode.as_str().to_string(),
+            DisplayOption::Monitor => (display.monitor + 1).to_string(),
+            DisplayOption::Resolution => match display.resolution {
+                Some((width, height)) => format!("{width}x{height}"),
+                None => "desktop".to_string(),
+            },
+            DisplayOption::Vsync => if display.vsync { "on" } else { "off" }.to_string(),
+            DisplayOption::FpsCap => match display.fps_cap {
+                0 => "unlimited".to_string(),
+                fps => fps.to_string(),
+            },
+        }
+    }
+
+    /// Mode, monitor and resolution changes revert unless confirmed
+    pub fn needs_confirm(&self) -> bool {
+        matches!(
+            self,
+            DisplayOption::Mode | DisplayOption::Monitor | DisplayOption::Resolution
+        )
+    }
+}
+
+/// Applies everything once, then only the present mode while the window stays the same,
+/// so toggling vsync doesn't move a window the player placed
+fn apply_display(
+    mut settings: ResMut<Settings>,
+    monitors: Query<(Entity, &Monitor)>,
+    added: Query<(), Added<Monitor>>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+    mut applied: Local<Option<DisplaySettings>>,
+) {
+    let unchanged = applied
+        .as_ref()
+        .is_some_and(|last| *last == settings.display);
+    if unchanged && added.is_empty() {
+        return;
+    }
+    let monitors = monitor_list(monitors.iter());
+    // settings.ron can name a monitor or a resolution this machine doesn't have
+    let mut clamped = settings.display.clone();
+    if clamped.clamp(&monitors) {
+        warn!("display settings not supported by the monitors, using {clamped:?}");
+        settings.display = clamped;
+    }
+    let display = &settings.display;
+    let same_window = applied
+        .as_ref()
+        .is_some_and(|last| last.same_window(display));
+    // monitors showing up can turn the kept video mode into the saved one
+    let fullscreen = display.mode == ScreenMode::Fullscreen && !added.is_empty();
+    if same_window && !fullscreen {
+        window.present_mode = display.present_mode();
+    } else {
+        display.apply(&mut window, monitors.get(display.monitor).copied());
+    }
+    *applied = Some(display.clone());
+}
+
+/// Sleeps out the rest of the frame, vsync alone can't go below the refresh rate.
+/// The graphics benchmark of [`Quality::Detect`] runs uncapped, it would measure the cap otherwise.
+#[cfg(not(target_arch = "wasm32"))]
+fn limit_frame_rate(
+    settings: Option<Res<Settings>>,
+    mut frame_start: Local<Option<std::time::Instant>>,
+) {
+    use std::time::{Duration, Instant};
+
+    let fps = settings
+        .filter(|s| s.graphics.quality != Quality::Detect)
+        .map(|s| s.display.fps_cap)
+        .unwrap_or_default();
+    if let (Some(start), true) = (*frame_start, fps > 0) {
+        let frame = Duration::from_secs_f64(1.0 / fps as f64);
+        let elapsed = start.elapsed();
+        if elapsed < frame {
+            std::thread::sleep(frame - elapsed);
+        }
+    }
+    *frame_start = Some(Instant::now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::window::VideoMode;
+
+    fn monitor(sizes: &[(u32, u32)]) -> Monitor {
+        Monitor {
+            name: None,
+            physical_height: 1080,
+            physical_width: 1920,
+            physical_position: IVec2::ZERO,
+            refresh_rate_millihertz: Some(60_000),
+            scale_factor: 1.0,
+            video_modes: sizes
+                .iter()
+                .map(|(width, height)| VideoMode {
+                    physical_size: UVec2::new(*width, *height),
+                    bit_depth: 32,
+                    refresh_rate_millihertz: 60_000,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn clamp_keeps_supported_settings() {
+        let first = monitor(&[(1280, 720), (1920, 1080)]);
+        let mut display = DisplaySettings {
+            resolution: Some((1280, 720)),
+            ..default()
+        };
+        assert!(!display.clamp(&[&first]));
+        assert_eq!(display.resolution, Some((1280, 720)));
+    }
+
+    #[test]
+    fn clamp_resets_missing_monitor() {
+        let first = monitor(&[(1920, 1080)]);
+        let mut display = DisplaySettings {
+            monitor: 2,
+            resolution: Some((1920, 1080)),
+            ..default()
+        };
+        assert!(display.clamp(&[&first]));
+        assert_eq!(display.monitor, 0);
+        assert_eq!(display.resolution, None);
+    }
+
+    #[test]
+    fn clamp_resets_unsupported_resolution() {
+        let first = monitor(&[(1920, 1080)]);
+        let second = monitor(&[(2560, 1440)]);
+        let mut display = DisplaySettings {
+            monitor: 1,
+            resolution: Some((1920, 1080)),
+            ..default()
+        };
+        assert!(display.clamp(&[&first, &second]));
+        assert_eq!(display.monitor, 1);
+        assert_eq!(display.resolution, None);
+    }
+
+    #[test]
+    fn clamp_waits_for_monitors() {
+        let mut display = DisplaySettings {
+            monitor: 3,
+            ..default()
+        };
+        assert!(!display.clamp(&[]));
+        assert_eq!(display.monitor, 3);
+    }
+}
@@ -14,6 +14,12 @@ pub fn plugin(app: &mut App) {
         .add_event::<OnCamCursorToggle>()
         .add_event::<OnDebugUiToggle>()
         .add_event::<OnDiagnosticsToggle>()
+        .add_event::<OnDamage>()
+        .add_event::<OnDeath>()
+        .add_event::<OnNotify>()
+        .add_event::<OnConsoleCommand>()
+        .add_event::<OnSaveGame>()
+        .add_event::<OnLoadGame>()
         .add_observer(pause)
         .add_observer(mute)
         .add_observer(back);
@@ -43,6 +49,43 @@ pub struct OnMuteToggle;
 pub struct OnDiagnosticsToggle;
 #[derive(Event)]
 pub struct OnDebugUiToggle;
+
+/// Short message for the player, shown as a toast on any screen
+#[derive(Event, Deref, Clone, Debug)]
+pub struct OnNotify(pub String);
+/// Line entered in the debug console, observers pick the commands they know by the first word
+#[derive(Event, Deref)]
+pub struct OnConsoleCommand(pub String);
+
+/// Write the running game into a save slot
+#[derive(Event, Deref)]
+pub struct OnSaveGame(pub usize);
+/// Start gameplay from a save slot
+#[derive(Event, Deref)]
+pub struct OnLoadGame(pub usize);
+
+/// Melee hit landed on an entity with [`Health`]
+#[derive(Event, Clone, Debug)]
+pub struct OnDamage {
+    pub target: Entity,
+    pub source: Entity,
+    pub amount: f32,
+    /// Shove applied through the target's `TnuaController`, if it has one
+    pub knockback: Vec3,
+}
+
+/// [`Health`] of the target ran out from the hit of `source`
+#[derive(Event, Clone, Debug)]
+pub struct OnDeath {
+    pub target: Entity,
+    pub source: Entity,
+}
+
+/// Animation events of attack clips, triggered on the [`AnimationPlayer`] entity
+#[derive(Event, Clone, Debug)]
+pub struct HitboxOn;
+#[derive(Event, Clone, Debug)]
+pub struct HitboxOff;
 #[derive(Event)]
 pub struct SwitchInputCtx {
     pub ctx: Context,
@@ -0,0 +1,231 @@
+use super::*;
+use std::ops::RangeInclusive;
+
+/// Overall graphics quality, picking a preset overwrites every [`Graphics`] option
+#[derive(Reflect, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quality {
+    /// Benchmark on the next start and pick a preset from the frame rate, the first run default
+    #[default]
+    Detect,
+    Low,
+    Medium,
+    High,
+    Ultra,
+    /// An option was changed after picking a preset
+    Custom,
+}
+
+impl Quality {
+    pub const PRESETS: [Quality; 4] =
+        [Quality::Low, Quality::Medium, Quality::High, Quality::Ultra];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Quality::Detect => "Detect",
+            Quality::Low => "Low",
+            Quality::Medium => "Medium",
+            Quality::High => "High",
+            Quality::Ultra => "Ultra",
+            Quality::Custom => "Custom",
+        }
+    }
+
+    /// Preset for the average frame rate of the benchmark, measured with the High preset.
+    /// Vsync caps the frame rate, so Ultra is only picked on high refresh rate displays.
+    pub fn from_fps(fps: f32) -> Self {
+        match fps {
+            fps if fps >= 110.0 => Quality::Ultra,
+            fps if fps >= 55.0 => Quality::High,
+            fps if fps >= 30.0 => Quality::Medium,
+            _ => Quality::Low,
+        }
+    }
+
+    /// Next preset for the settings button, wrapping around
+    pub fn next(&self) -> Self {
+        let i = Self::PRESETS.iter().position(|q| q == self);
+        Self::PRESETS[i.map(|i| (i + 1) % Self::PRESETS.len()).unwrap_or(0)]
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AntiAliasing {
+    Off,
+    Msaa,
+    Smaa,
+}
+
+impl AntiAliasing {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AntiAliasing::Off => "Off",
+            AntiAliasing::Msaa => "MSAA",
+            AntiAliasing::Smaa => "SMAA",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            AntiAliasing::Off => AntiAliasing::Msaa,
+            AntiAliasing::Msaa => AntiAliasing::Smaa,
+            AntiAliasing::Smaa => AntiAliasing::Off,
+        }
+    }
+}
+
+/// Resolution and sample counts of the atmosphere lookup textures
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detail {
+    Low,
+    Medium,
+    High,
+}
+
+impl Detail {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Detail::Low => "Low",
+            Detail::Medium => "Medium",
+            Detail::High => "High",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Detail::Low => Detail::Medium,
+            Detail::Medium => Detail::High,
+            Detail::High => Detail::Low,
+        }
+    }
+}
+
+/// Video options, applied live to every [`SceneCamera`] and the sky lights
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Graphics {
+    pub quality: Quality,
+    pub shadows: bool,
+    /// World units from the camera the last shadow cascade reaches
+    pub shadow_distance: f32,
+    pub cascades: usize,
+    pub bloom: bool,
+    pub atmosphere: Detail,
+    pub anti_aliasing: AntiAliasing,
+    /// Fraction of the window resolution the scene renders at before it is stretched over the window
+    pub render_scale: f32,
+    pub fog: bool,
+}
+
+impl Graphics {
+    pub const SHADOW_DISTANCE: RangeInclusive<f32> = 20.0..=500.0;
+    pub const CASCADES: RangeInclusive<usize> = 1..=4;
+    pub const RENDER_SCALE: RangeInclusive<f32> = 0.5..=1.0;
+    const SHADOW_DISTANCE_STEP: f32 = 25.0;
+    const RENDER_SCALE_STEP: f32 = 0.05;
+
+    pub fn preset(quality: Quality) -> Self {
+        let (shadows, shadow_distance, cascades, bloom, atmosphere, anti_aliasing, render_scale) =
+            match quality {
+                Quality::Low => (false, 50.0, 1, false, Detail::Low, AntiAliasing::Off, 0.75),
+                Quality::Medium => (
+                    true,
+                    100.0,
+                    2,
+                    true,
+                    Detail::Medium,
+                    AntiAliasing::Smaa,
+                    1.0,
+                ),
+                Quality::Ultra => (true, 300.0, 4, true, Detail::High, AntiAliasing::Msaa, 1.0),
+                Quality::High | Quality::Detect | Quality::Custom => (
+                    true,
+                    200.0,
+                    3,
+                    true,
+                    Detail::Medium,
+                    AntiAliasing::Msaa,
+                    1.0,
+                ),
+            };
+        Self {
+            quality,
+            shadows,
+            shadow_distance,
+            cascades,
+            bloom,
+            atmosphere,
+            anti_aliasing,
+            render_scale,
+            fog: true,
+        }
+    }
+
+    /// Steps a number by `step` increments, or toggles and cycles everything else.
+    /// Changing the quality applies the next preset, any other option makes it [`Quality::Custom`].
+    pub fn change(&mut self, option: GraphicsOption, step: i32) {
+        match option {
+            GraphicsOption::Quality => {
+                *self = Self::preset(self.quality.next());
+                return;
+            }
+            GraphicsOption::Shadows => self.shadows = !self.shadows,
+            GraphicsOption::ShadowDistance => {
+                let distance = self.shadow_distance + step as f32 * Self::SHADOW_DISTANCE_STEP;
+                self.shadow_distance =
+                    distance.clamp(*Self::SHADOW_DISTANCE.start(), *Self::SHADOW_DISTANCE.end());
+            }
+            GraphicsOption::Cascades => {
+                let cascades = self.cascades.saturating_add_signed(step as isize);
+                self.cascades = cascades.clamp(*Self::CASCADES.start(), *Self::CASCADES.end());
+            }
+            GraphicsOption::Bloom => self.bloom = !self.bloom,
+            GraphicsOption::Atmosphere => self.atmosphere = self.atmosphere.next(),
+            GraphicsOption::AntiAliasing => self.anti_aliasing = self.anti_aliasing.next(),
+            GraphicsOption::RenderScale => {
+                let scale = self.render_scale + step as f32 * Self::RENDER_SCALE_STEP;
+                self.render_scale =
+                    scale.clamp(*Self::RENDER_SCALE.start(), *Self::RENDER_SCALE.end());
+            }
+            GraphicsOption::Fog => self.fog = !self.fog,
+        }
+        self.quality = Quality::Custom;
+    }
+}
+
+impl Default for Graphics {
+    fn default() -> Self {
+        Self::preset(Quality::Detect)
+    }
+}
+
+/// Settings row of a [`Graphics`] option, its label is refreshed when settings change
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+pub enum GraphicsOption {
+    Quality,
+    Shadows,
+    ShadowDistance,
+    Cascades,
+    Bloom,
+    Atmosphere,
+    AntiAliasing,
+    RenderScale,
+    Fog,
+}
+
+impl GraphicsOption {
+    pub fn value(&self, graphics: &Graphics) -> String {
+        let on_off = |on: bool| if on { "on" } else { "off" }.to_string();
+        match self {
+            GraphicsOption::Quality => graphics.quality.as_str().to_string(),
+            GraphicsOption::Shadows => on_off(graphics.shadows),
+            GraphicsOption::ShadowDistance => format!("{:.0}", graphics.shadow_distance),
+            GraphicsOption::Cascades => graphics.cascades.to_string(),
+            GraphicsOption::Bloom => on_off(graphics.bloom),
+            GraphicsOption::Atmosphere => graphics.atmosphere.as_str().to_string(),
+            GraphicsOption::AntiAliasing => graphics.anti_aliasing.as_str().to_string(),
+            GraphicsOption::RenderScale => format!("{:.0}%", graphics.render_scale * 100.0),
+            GraphicsOption::Fog => on_off(graphics.fog),
+        }
+    }
+}
@@ -10,14 +10,17 @@ pub fn plugin(app: &mut App) {
         .add_observer(bind_gameplay);
 }
 
+/// Degrees per second the camera turns with the stick fully tilted at sensitivity 1
+const GAMEPAD_TURN_SPEED: f32 = 120.0;
+
 fn spawn_ctx(mut cmds: Commands) {
     cmds.spawn((GlobalInputCtx, Actions::<ModalCtx>::default(), ModalCtx));
 }
 
 /// Context switch observer
 /// We need global context to handle input on main menu, when no players spawned yet
-/// but we have to reset it manually
-/// TODO: check when split screen ready
+/// but we have to reset it manually.
+/// Players are switched one by one by their entity, so it works the same in split screen.
 fn on_ctx_switch(
     on: Trigger<SwitchInputCtx>,
     mut commands: Commands,
@@ -79,15 +82,32 @@ pub enum Context {
     Gameplay,
 }
 
-/// TODO: figure out split screen
 /// Used as both input context and component.
-#[derive(InputContext, Component, Clone, Copy)]
-pub struct GameplayCtx;
+///
+/// Every player owns one, `slot` is the index in the [`Lobby`]
+/// and `gamepad` is the joined gamepad entity (`None` means any gamepad, used in single player).
+#[derive(InputContext, Component, Default, Clone, Copy, Debug)]
+pub struct GameplayCtx {
+    pub slot: usize,
+    pub gamepad: Option<Entity>,
+}
+
+impl GameplayCtx {
+    pub fn new(slot: usize, gamepad: Option<Entity>) -> Self {
+        Self { slot, gamepad }
+    }
+
+    /// Keyboard and mouse are bound only to the first player
+    pub fn has_keyboard(&self) -> bool {
+        self.slot == 0
+    }
+}
 
 #[derive(Debug, InputAction)]
 #[input_action(output = Vec2)]
 pub struct Navigate;
 
+/// Camera orbit in degrees this frame, x turns around the player and y tilts up
 #[derive(Debug, InputAction)]
 #[input_action(output = Vec2, require_reset = true)]
 pub struct Rotate;
@@ -144,59 +164,93 @@ pub struct RightTab;
 #[input_action(output = bool)]
 pub struct LeftTab;
 
+/// Builds gameplay bindings from [`Keybind`] and [`GamepadBind`] stored in [`Settings`].
+///
+/// Runs every time [`Actions<GameplayCtx>`] is inserted and on [`RebuildBindings`],
+/// so applying new bindings in the keybind editor takes effect without a restart.
 fn bind_gameplay(
     trigger: Trigger<Binding<GameplayCtx>>,
+    settings: Res<Settings>,
     mut context: Query<(&GameplayCtx, &mut Actions<GameplayCtx>)>,
 ) {
-    let (&_id, mut actions) = context
+    let (&ctx, mut actions) = context
         .get_mut(trigger.target())
         .expect("Failed to query gameplay context actions");
+    let keybind = &settings.keybind;
+    let gamepad = &settings.gamepad;
 
-    // let gamepad_entity = gamepads.iter().nth(id as usize);
-    // actions.set_gamepad(gamepad_entity.unwrap_or(Entity::PLACEHOLDER));
+    // Each co-op player only listens to the gamepad that joined the lobby,
+    // single player accepts input from any of them.
+    match ctx.gamepad {
+        Some(gamepad_entity) => actions.set_gamepad(gamepad_entity),
+        None => actions.set_gamepad(GamepadDevice::Any),
+    };
 
+    // GAMEPAD
     actions
         .bind::<Navigate>()
-        .to((Cardinal::wasd_keys(), Axial::left_stick()))
+        .to(gamepad.movement.axial())
         .with_modifiers((
-            DeadZone::default(), // Apply non-uniform normalization to ensure consistent speed, otherwise diagonal movement will be faster.
+            DeadZone {
+                lower_threshold: gamepad.deadzone,
+                ..Default::default()
+            }, // Apply non-uniform normalization to ensure consistent speed, otherwise diagonal movement will be faster.
             Scale::splat(0.3), // Additionally multiply by a constant to achieve the desired speed.
         ));
-
-    actions.bind::<Rotate>().to((
-        Input::mouse_motion().with_modifiers((Scale::splat(0.1), Negate::all())),
-        Axial::right_stick().with_modifiers_each((Scale::splat(2.0), Negate::x())),
-    ));
-
+    // a held stick keeps turning, scaled by the frame time to degrees per second
+    actions
+        .bind::<Rotate>()
+        .to(gamepad.camera.axial().with_modifiers_each((
+            DeadZone {
+                lower_threshold: gamepad.deadzone,
+                ..Default::default()
+            },
+            Scale::splat(GAMEPAD_TURN_SPEED * gamepad.sensitivity),
+            DeltaScale,
+            Negate {
+                x: true,
+                y: gamepad.invert_y,
+                z: false,
+            },
+        )));
+    actions.bind::<Escape>().to(&gamepad.menu);
+    actions.bind::<Crouch>().to(&gamepad.crouch);
+    actions.bind::<Jump>().to(&gamepad.jump);
+    actions.bind::<Dash>().to(&gamepad.dash);
+    actions.bind::<Sprint>().to(&gamepad.sprint);
+    actions.bind::<Attack>().to(&gamepad.attack);
+
+    // KEYBOARD AND MOUSE
+    // There is only one keyboard, it goes to the first player
+    if !ctx.has_keyboard() {
+        return;
+    }
+    actions.bind::<Navigate>().to(Cardinal {
+        north: &keybind.forward,
+        east: &keybind.right,
+        south: &keybind.backward,
+        west: &keybind.left,
+    });
+    // the camera crate orbits and zooms with the mouse on its own
     actions.bind::<Pause>().to(KeyCode::KeyP);
     actions.bind::<Mute>().to(KeyCode::KeyM);
-    actions
-        .bind::<Escape>()
-        .to((KeyCode::Escape, GamepadButton::Select));
-    actions
-        .bind::<Crouch>()
-        .to((KeyCode::ControlLeft, GamepadButton::East));
-    actions
-        .bind::<Jump>()
-        .to((KeyCode::Space, GamepadButton::South));
-    actions
-        .bind::<Dash>()
-        .to((KeyCode::AltLeft, GamepadButton::LeftTrigger));
-    actions
-        .bind::<Sprint>()
-        .to((KeyCode::ShiftLeft, GamepadButton::LeftThumb));
-    actions
-        .bind::<Attack>()
-        .to((MouseButton::Left, GamepadButton::RightTrigger2));
+    actions.bind::<Escape>().to(KeyCode::Escape);
+    actions.bind::<Crouch>().to(&keybind.crouch);
+    actions.bind::<Jump>().to(&keybind.jump);
+    actions.bind::<Dash>().to(&keybind.dash);
+    actions.bind::<Sprint>().to(&keybind.sprint);
+    actions.bind::<Attack>().to(&keybind.attack);
 }
 
 fn bind_modal(
     trigger: Trigger<Binding<ModalCtx>>,
+    settings: Res<Settings>,
     mut menus: Query<(&ModalCtx, &mut Actions<ModalCtx>)>,
 ) {
     let (&_id, mut actions) = menus
         .get_mut(trigger.target())
         .expect("Failed to get modal context id");
+    let gamepad = &settings.gamepad;
 
     // let gamepad_entity = gamepads.iter().nth(id as usize);
     // actions.set_gamepad(gamepad_entity.unwrap_or(Entity::PLACEHOLDER));
@@ -204,15 +258,18 @@ fn bind_modal(
     actions.bind::<NavigateModal>().to((
         Cardinal::wasd_keys(),
         Input::mouse_motion().with_modifiers((Scale::splat(0.1), Negate::all())),
-        Axial::right_stick().with_modifiers_each((Scale::splat(2.0), Negate::x())),
+        gamepad
+            .camera
+            .axial()
+            .with_modifiers_each((Scale::splat(2.0), Negate::x())),
     ));
 
     actions
         .bind::<Escape>()
-        .to((KeyCode::Escape, GamepadButton::East));
+        .to((KeyCode::Escape, &gamepad.back));
     actions
         .bind::<Select>()
-        .to((KeyCode::Enter, GamepadButton::South, MouseButton::Left));
+        .to((KeyCode::Enter, &gamepad.select, MouseButton::Left));
     actions.bind::<RightTab>().to(GamepadButton::RightTrigger);
     actions.bind::<LeftTab>().to(GamepadButton::LeftTrigger);
 }
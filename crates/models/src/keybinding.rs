@@ -52,3 +52,91 @@ impl Default for Keybind {
         }
     }
 }
+
+/// Gamepad settings.
+///
+/// Buttons are stored as [`Input`] as well, so the keybind editor can reuse the same widgets
+/// and conflict detection for both profiles. Sticks are bidirectional, so they are assigned
+/// as a whole with [`GamepadStick`] instead of per direction.
+#[derive(Resource, Reflect, Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct GamepadBind {
+    pub jump: Vec<Input>,
+    pub dash: Vec<Input>,
+    pub crouch: Vec<Input>,
+    pub sprint: Vec<Input>,
+    pub attack: Vec<Input>,
+    pub menu: Vec<Input>,
+    // menus
+    pub select: Vec<Input>,
+    pub back: Vec<Input>,
+    // sticks
+    pub movement: GamepadStick,
+    pub camera: GamepadStick,
+    pub deadzone: f32,
+    pub sensitivity: f32,
+    pub invert_y: bool,
+}
+
+impl GamepadBind {
+    pub const MIN_DEADZONE: f32 = 0.0;
+    pub const MAX_DEADZONE: f32 = 0.9;
+    pub const MIN_SENSITIVITY: f32 = 0.1;
+    pub const MAX_SENSITIVITY: f32 = 5.0;
+
+    /// Clears button bindings, stick settings are left untouched.
+    pub fn clear(&mut self) {
+        self.jump.clear();
+        self.dash.clear();
+        self.crouch.clear();
+        self.sprint.clear();
+        self.attack.clear();
+        self.menu.clear();
+    }
+
+    pub fn swap_sticks(&mut self) {
+        std::mem::swap(&mut self.movement, &mut self.camera);
+    }
+}
+
+impl Default for GamepadBind {
+    fn default() -> Self {
+        Self {
+            jump: vec![GamepadButton::South.into()],
+            dash: vec![GamepadButton::LeftTrigger.into()],
+            crouch: vec![GamepadButton::East.into()],
+            sprint: vec![GamepadButton::LeftThumb.into()],
+            attack: vec![GamepadButton::RightTrigger2.into()],
+            menu: vec![GamepadButton::Select.into()],
+            select: vec![GamepadButton::South.into()],
+            back: vec![GamepadButton::East.into()],
+            movement: GamepadStick::Left,
+            camera: GamepadStick::Right,
+            deadzone: 0.1,
+            sensitivity: 1.0,
+            invert_y: false,
+        }
+    }
+}
+
+#[derive(Reflect, Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamepadStick {
+    Left,
+    Right,
+}
+
+impl GamepadStick {
+    pub fn axial(&self) -> Axial<GamepadAxis> {
+        match self {
+            GamepadStick::Left => Axial::left_stick(),
+            GamepadStick::Right => Axial::right_stick(),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GamepadStick::Left => "Left",
+            GamepadStick::Right => "Right",
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use super::*;
+use avian3d::prelude::*;
+
+/// Collision layers, colliders without [`CollisionLayers`] are on [`GameLayer::Default`]
+/// and collide with everything
+#[derive(
+    PhysicsLayer, Reflect, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum GameLayer {
+    /// The level and props
+    #[default]
+    Default,
+    Player,
+    /// Third person cameras, kept out of other cameras' boom casts
+    Camera,
+}
@@ -3,35 +3,71 @@ use bevy_enhanced_input::prelude::*;
 use bevy_seedling::prelude::*;
 use serde::{Deserialize, Serialize};
 
+mod animation;
+mod collectible;
+mod combat;
+mod display;
 mod event_dispatch;
 mod ext_traits;
+mod graphics;
 mod input;
 mod keybinding;
+mod layers;
+mod music;
 mod palette;
 mod player;
 mod pre_load;
 mod primitives;
+mod ron_handle;
+mod save;
 mod settings;
 mod states;
+mod storage;
+mod surface;
+mod time_of_day;
+mod tutorial;
+mod weather;
 
+pub use animation::*;
+pub use collectible::*;
+pub use combat::*;
+pub use display::*;
 pub use event_dispatch::*;
 pub use ext_traits::*;
+pub use graphics::*;
 pub use input::*;
 pub use keybinding::*;
+pub use layers::*;
+pub use music::*;
 pub use palette::*;
 pub use player::*;
 pub use pre_load::*;
 pub use primitives::*;
+pub use ron_handle::*;
+pub use save::*;
 pub use settings::*;
 pub use states::*;
+pub use storage::*;
+pub use surface::*;
+pub use time_of_day::*;
+pub use tutorial::*;
+pub use weather::*;
 
 pub fn plugin(app: &mut App) {
     app.add_plugins((
         settings::plugin,
+        display::plugin,
         states::plugin,
         input::plugin,
         event_dispatch::plugin,
     ));
+    // components authored in Blender through Skein
+    app.register_type::<Health>()
+        .register_type::<Collectible>()
+        .register_type::<MusicZone>()
+        .register_type::<Boombox>()
+        .register_type::<SurfaceMaterial>()
+        .register_type::<WeatherZone>();
 }
 
 /// The game's main screen states.
@@ -44,7 +80,10 @@ pub enum Screen {
     Splash,
     // During the loading State the LoadingPlugin will load our assets
     Loading,
+    // Intro to the tutorial, which itself runs in Gameplay
     Tutorial,
+    // Gamepads join here before a co-op session
+    Lobby,
     Credits,
     Settings,
     // Here the menu is drawn and waiting for player interaction
@@ -67,6 +106,7 @@ pub enum AppSystems {
 pub enum Modal {
     Main,
     Settings,
+    Save,
 }
 
 #[derive(Reflect, Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,62 @@
+use super::*;
+use avian3d::prelude::*;
+use bevy::asset::LoadContext;
+
+/// What the soundtrack should feel like, tracks are tagged with moods in `config.ron`
+#[derive(Reflect, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mood {
+    #[default]
+    Menu,
+    Explore,
+    Combat,
+    Credits,
+}
+
+/// Scene volume switching the soundtrack mood while a player is inside,
+/// authored in Blender through Skein together with its collider.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component, Default)]
+#[require(Sensor, CollisionEventsEnabled)]
+pub struct MusicZone {
+    pub mood: Mood,
+}
+
+/// `music` section of `config.ron`, its tracks load with the config
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
+pub struct MusicConfig {
+    /// Seconds to fade the old track out, and the same again to fade the new one in
+    pub fade: f32,
+    /// Seconds the [`Mood::Combat`] music keeps playing after the last hit
+    pub combat_linger: f32,
+    pub tracks: Vec<Track>,
+    /// Track looping from every [`Boombox`] in the scene
+    pub boombox: RonHandle<Sample>,
+    /// Meters around a boombox in which the soundtrack fades out under it
+    pub boombox_duck: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
+pub struct Track {
+    pub path: RonHandle<Sample>,
+    pub moods: Vec<Mood>,
+}
+
+impl MusicConfig {
+    /// Tracks tagged with the mood
+    pub fn tracks(&self, mood: Mood) -> Vec<&RonHandle<Sample>> {
+        self.tracks
+            .iter()
+            .filter(|t| t.moods.contains(&mood))
+            .map(|t| &t.path)
+            .collect()
+    }
+}
+
+impl RonDependencies for MusicConfig {
+    fn load_dependencies(&mut self, load_context: &mut LoadContext) {
+        for track in self.tracks.iter_mut() {
+            track.path.load(load_context);
+        }
+        self.boombox.load(load_context);
+    }
+}
@@ -8,6 +8,8 @@ pub struct Player {
     pub speed: f32,
     pub animation_state: AnimationState,
     pub animations: HashMap<String, AnimationNodeIndex>,
+    /// Combo strike clips carrying hitbox animation events
+    pub attacks: Vec<AnimationNodeIndex>,
 }
 
 impl Default for Player {
@@ -17,6 +19,7 @@ impl Default for Player {
             speed: 1.0,
             animation_state: AnimationState::StandIdle,
             animations: HashMap::new(),
+            attacks: Vec::new(),
         }
     }
 }
@@ -39,4 +42,29 @@ pub enum AnimationState {
     WallSlide,
     WallJump,
     KnockBack,
+    /// Melee combo strike
+    Attack(usize),
 }
+
+impl AnimationState {
+    /// Playback speed carried by the state, `1.0` for states without one
+    pub fn speed(&self) -> f32 {
+        match self {
+            AnimationState::Run(speed)
+            | AnimationState::Sprint(speed)
+            | AnimationState::Climb(speed)
+            | AnimationState::Crawl(speed) => *speed,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Camera following this player, each co-op player has its own viewport
+#[derive(Component, Reflect, Deref, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct PlayerCamera(pub Entity);
+
+/// [`AnimationPlayer`] entity somewhere down in the player model hierarchy
+#[derive(Component, Reflect, Deref, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct PlayerAnimator(pub Entity);
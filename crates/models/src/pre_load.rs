@@ -1,18 +1,28 @@
-use bevy::prelude::*;
+use crate::{DayConfig, GameLayer, MusicConfig, RonDependencies};
+use bevy::{asset::LoadContext, prelude::*};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize, Reflect, Asset, Resource)]
 #[reflect(Resource)]
 pub struct Config {
     pub sound: Sound,
+    pub music: MusicConfig,
     pub physics: Physics,
     pub player: PlayerConfig,
     pub credits: Credits,
     pub settings: SettingsPreloaded,
     pub timers: Timers,
+    pub time_of_day: DayConfig,
+}
+
+impl RonDependencies for Config {
+    fn load_dependencies(&mut self, load_context: &mut LoadContext) {
+        self.music.load_dependencies(load_context);
+    }
 }
 
 #[derive(Resource, Debug, Clone, Serialize, Deserialize, Reflect)]
+#[serde(default)]
 pub struct Sound {
     pub general: f32,
     pub music: f32,
@@ -34,24 +44,70 @@ pub struct Physics {
     pub distance_fog: bool,
     pub fog_directional_light_exponent: f32,
     pub fog_visibility: f32,
-    pub shadow_distance: f32,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
 pub struct PlayerConfig {
     pub movement: Movement,
     pub hitbox: Hitbox,
-    pub zoom: (f32, f32),
+    pub combat: Combat,
+    pub camera: CameraConfig,
     pub fov: f32,
     pub spawn_pos: (f32, f32, f32),
 }
 
+/// Third person camera boom following each player
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
+pub struct CameraConfig {
+    /// Closest and farthest the player can zoom
+    pub zoom: (f32, f32),
+    /// Radius of the sphere cast from the player to the camera, keeps the near plane out of walls
+    pub probe_radius: f32,
+    /// World units per second the boom grows back once nothing is in the way, shortening is instant
+    pub recovery_speed: f32,
+    /// Layers shortening the boom, and fading when `fade_occluders` is on
+    pub collision_layers: Vec<GameLayer>,
+    /// Fade meshes between the camera and the player instead of leaving them opaque
+    pub fade_occluders: bool,
+    /// Alpha of fully faded meshes
+    pub fade_alpha: f32,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
 pub struct Hitbox {
     pub radius: f32,
     pub height: f32,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
+pub struct Combat {
+    pub health: f32,
+    /// Damage per combo strike, the combo is as long as `attacks` in `animations.ron`
+    pub damage: Vec<f32>,
+    pub knockback: f32,
+    /// Seconds after a swing to continue the combo
+    pub combo_window: f32,
+    /// Seconds a hit character can't act
+    pub hit_stun: f32,
+    /// Radius of the sphere cast in front of the player
+    pub hitbox_radius: f32,
+    /// Distance of the sphere cast
+    pub reach: f32,
+    /// Co-op players can hit each other
+    pub friendly_fire: bool,
+}
+
+impl Combat {
+    /// Damage of the strike, the last value is reused for longer combos
+    pub fn damage(&self, step: usize) -> f32 {
+        self.damage
+            .get(step)
+            .or(self.damage.last())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
 pub struct Movement {
     pub actions_in_air: u8,
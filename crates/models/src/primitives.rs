@@ -13,24 +13,36 @@ macro_rules! declare_markers {
 
 declare_markers!(
     SceneCamera,
+    // the camera of the first player, rendering the whole window outside of split screen
+    MainCamera,
     BgMusic,
     // scene
+    Level,
     Sun,
     Moon,
-    // TODO: The idea is to create a boombox with spatial audio
-    // <https://github.com/bevyengine/bevy/blob/main/examples/audio/spatial_audio_3d.rs>
+    // scene object playing `music.boombox` from `config.ron` with spatial audio
     Boombox,
     SunCycleLabel,
+    LobbyLabel,
     // user input context
     GlobalInputCtx,
     // UI: mostly for nodes or labels that have to change visibility or content at some point
     PerfUi,
     GameplayUi,
+    ScoreLabel,
+    LoadingBar,
+    LoadingLabel,
+    LoadingErrorScreen,
+    TutorialPrompt,
+    DebugConsole,
+    ConsoleInput,
     PauseIcon,
     MuteIcon,
     MenuModal,
     // settings
     SettingsModal,
+    SaveModal,
+    SaveSlots,
     TabBar,
     TabContent,
     DisabledButton,
@@ -43,7 +55,14 @@ declare_markers!(
     DiagnosticsLabel,
     DebugUiLabel,
     SaveSettingsLabel,
-    FovLabel
+    // display changes revert unless kept before the countdown ends
+    DisplayConfirm,
+    DisplayConfirmLabel,
+    FovLabel,
+    GamepadStickLabel,
+    InvertYLabel,
+    GamepadDeadzoneLabel,
+    GamepadSensitivityLabel
 );
 
 macro_rules! timers {
@@ -55,7 +74,7 @@ macro_rules! timers {
         )*
     };
 }
-timers!(JumpTimer, StepTimer);
+timers!(JumpTimer, StepTimer, HitStun, ToastTimer);
 
 // macro_rules! sliders {
 //   ( $( $name:ident ),* ) => {
@@ -0,0 +1,47 @@
+use bevy::asset::{LoadContext, UntypedAssetId, VisitAssetDependencies};
+use bevy::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// RON assets that reference other assets with [`RonHandle`] fields.
+/// Load them here so they become dependencies of the RON asset.
+pub trait RonDependencies {
+    fn load_dependencies(&mut self, load_context: &mut LoadContext);
+}
+
+/// Handle to another asset, written in RON as its path: `"models/rock.glb"`.
+/// Labelled sub-assets work too: `"models/rock.glb#Scene0"`.
+/// Stays a default handle until [`RonHandle::load`] is called from [`RonDependencies`].
+#[derive(Debug, Clone, Deref, Reflect)]
+pub struct RonHandle<A: Asset> {
+    pub path: String,
+    #[deref]
+    pub handle: Handle<A>,
+}
+
+impl<A: Asset> RonHandle<A> {
+    pub fn load(&mut self, load_context: &mut LoadContext) {
+        self.handle = load_context.load(self.path.clone());
+    }
+}
+
+impl<'de, A: Asset> Deserialize<'de> for RonHandle<A> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let path = String::deserialize(deserializer)?;
+        Ok(Self {
+            path,
+            handle: Handle::default(),
+        })
+    }
+}
+
+impl<A: Asset> Serialize for RonHandle<A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.path.serialize(serializer)
+    }
+}
+
+impl<A: Asset> VisitAssetDependencies for RonHandle<A> {
+    fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId)) {
+        self.handle.visit_dependencies(visit);
+    }
+}
@@ -0,0 +1,223 @@
+use super::*;
+use std::{
+    error::Error,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const SAVE_SLOTS: usize = 3;
+/// Bump on every schema change of [`SaveData`] and add a migration to [`MIGRATIONS`]
+pub const SAVE_VERSION: u32 = 2;
+
+/// Upgrades raw save data by one version: `MIGRATIONS[n]` takes version `n` to `n + 1`.
+///
+/// Migrations work on untyped [`ron::Value`] so they can rename, move or fill in
+/// fields that the current [`SaveData`] can no longer read.
+pub type Migration = fn(&mut ron::Map) -> Result<(), Box<dyn Error>>;
+pub const MIGRATIONS: &[Migration] = &[unversioned, sun_rotation_to_hour];
+
+/// Saves written before versioning had no `version` and no `collected` field
+fn unversioned(save: &mut ron::Map) -> Result<(), Box<dyn Error>> {
+    let collected = ron::Value::String("collected".into());
+    if save.get(&collected).is_none() {
+        save.insert(collected, ron::Value::Seq(vec![]));
+    }
+    Ok(())
+}
+
+/// Version 1 saved the sun and moon rotations, the hour of the [`TimeOfDay`] replaced them.
+/// The sun used to start at sunrise and only turn around the x axis.
+fn sun_rotation_to_hour(save: &mut ron::Map) -> Result<(), Box<dyn Error>> {
+    let sun = save
+        .remove(&ron::Value::String("sun".into()))
+        .map(|v| v.into_rust::<[f32; 4]>())
+        .transpose()?
+        .unwrap_or([0.0, 0.0, 0.0, 1.0]);
+    save.remove(&ron::Value::String("moon".into()));
+    let angle = 2.0 * sun[0].atan2(sun[3]);
+    let hour = (6.0 - angle * 12.0 / std::f32::consts::PI).rem_euclid(24.0);
+    save.insert(
+        ron::Value::String("hour".into()),
+        ron::Value::Number(hour.into()),
+    );
+    Ok(())
+}
+
+/// Snapshot of a run written to one of the [`SAVE_SLOTS`]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SaveData {
+    pub version: u32,
+    /// Seconds since the unix epoch, used to find the save to continue from
+    pub saved_at: u64,
+    /// Per co-op slot, in order
+    pub players: Vec<PlayerSave>,
+    pub score: i32,
+    /// [`TimeOfDay`] hour
+    pub hour: f32,
+    /// [`Name`]s of picked up [`Collectible`]s
+    pub collected: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PlayerSave {
+    pub translation: [f32; 3],
+    pub rotation: [f32; 4],
+    pub health: f32,
+}
+
+/// Save picked on the title screen, applied once gameplay is set up
+#[derive(Resource, Debug, Clone)]
+pub struct LoadedSave(pub SaveData);
+
+impl SaveData {
+    /// Save slots live in the platform data directory
+    pub fn dir() -> PathBuf {
+        data_dir().join("saves")
+    }
+
+    pub fn path(slot: usize) -> PathBuf {
+        Self::dir().join(format!("slot_{}.ron", slot + 1))
+    }
+
+    /// Falls back to the previous write of the slot if the last one is broken
+    pub fn read(slot: usize) -> Result<Self, Box<dyn Error>> {
+        read_with_backup(&Self::path(slot), Self::parse).map(ReadFile::logged)
+    }
+
+    /// Runs every migration newer than the save's version before deserializing
+    pub fn parse(content: &str) -> Result<Self, Box<dyn Error>> {
+        let ron::Value::Map(mut save) = ron::from_str::<ron::Value>(content)? else {
+            return Err("save is not a struct".into());
+        };
+        let version = save
+            .get(&ron::Value::String("version".into()))
+            .cloned()
+            .map(|v| v.into_rust::<u32>())
+            .transpose()?
+            .unwrap_or(0);
+        if version > SAVE_VERSION {
+            return Err(format!("save version {version} is newer than {SAVE_VERSION}").into());
+        }
+        for migrate in &MIGRATIONS[version as usize..] {
+            migrate(&mut save)?;
+        }
+        save.insert(
+            ron::Value::String("version".into()),
+            ron::Value::Number(SAVE_VERSION.into()),
+        );
+        Ok(ron::Value::Map(save).into_rust()?)
+    }
+
+    pub fn write(&mut self, slot: usize) -> Result<(), Box<dyn Error>> {
+        self.version = SAVE_VERSION;
+        self.saved_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let content = ron::ser::to_string_pretty(self, Default::default())?;
+        write_atomic(&Self::path(slot), &content)?;
+        Ok(())
+    }
+
+    /// Most recently written slot
+    pub fn latest() -> Option<usize> {
+        (0..SAVE_SLOTS)
+            .filter_map(|slot| Self::read(slot).ok().map(|save| (slot, save.saved_at)))
+            .max_by_key(|(_, saved_at)| *saved_at)
+            .map(|(slot, _)| slot)
+    }
+
+    /// Short description for slot buttons
+    pub fn describe(slot: usize) -> String {
+        match Self::read(slot) {
+            Ok(save) => format!(
+                "Slot {}: score {}, {} player(s)",
+                slot + 1,
+                save.score,
+                save.players.len()
+            ),
+            Err(_) => format!("Slot {}: empty", slot + 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Written before versioning, the sun a quarter turn past sunrise
+    const V0_SAVE: &str = "(
+        saved_at: 1700000000,
+        players: [(translation: (1.0, 2.0, 3.0), rotation: (0.0, 0.0, 0.0, 1.0), health: 40.0)],
+        score: 7,
+        sun: (-0.70710677, 0.0, 0.0, 0.70710677),
+        moon: (0.70710677, 0.0, 0.0, 0.70710677),
+    )";
+
+    fn hour_of(sun: &str) -> f32 {
+        let Ok(ron::Value::Map(mut save)) = ron::from_str(&format!("(sun: {sun})")) else {
+            panic!("sun is not a struct");
+        };
+        sun_rotation_to_hour(&mut save).unwrap();
+        save.get(&ron::Value::String("hour".into()))
+            .cloned()
+            .unwrap()
+            .into_rust()
+            .unwrap()
+    }
+
+    #[test]
+    fn v0_save_migrates_to_current() {
+        let save = SaveData::parse(V0_SAVE).unwrap();
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.saved_at, 1_700_000_000);
+        assert_eq!(save.score, 7);
+        assert_eq!(save.players.len(), 1);
+        assert_eq!(save.players[0].translation, [1.0, 2.0, 3.0]);
+        assert_eq!(save.players[0].health, 40.0);
+        assert!(save.collected.is_empty());
+        assert!((save.hour - 12.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn current_save_round_trips() {
+        let save = SaveData {
+            version: SAVE_VERSION,
+            hour: 21.5,
+            collected: vec!["Gem".into()],
+            ..default()
+        };
+        let content = ron::ser::to_string_pretty(&save, Default::default()).unwrap();
+        let read = SaveData::parse(&content).unwrap();
+        assert_eq!(read.hour, 21.5);
+        assert_eq!(read.collected, ["Gem"]);
+    }
+
+    #[test]
+    fn newer_saves_are_rejected() {
+        let content = format!("(version: {})", SAVE_VERSION + 1);
+        assert!(SaveData::parse(&content).is_err());
+    }
+
+    #[test]
+    fn sun_rotation_to_hour_follows_the_x_turn() {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+        // identity was sunrise
+        assert!(close(hour_of("(0.0, 0.0, 0.0, 1.0)"), 6.0));
+        assert!(close(hour_of("(-0.70710677, 0.0, 0.0, 0.70710677)"), 12.0));
+        assert!(close(hour_of("(-1.0, 0.0, 0.0, 0.0)"), 18.0));
+        assert!(close(hour_of("(0.70710677, 0.0, 0.0, 0.70710677)"), 0.0));
+        // no sun saved falls back to sunrise
+        let mut save = ron::Map::new();
+        sun_rotation_to_hour(&mut save).unwrap();
+        assert!(save.get(&ron::Value::String("moon".into())).is_none());
+        assert_eq!(
+            save.get(&ron::Value::String("hour".into()))
+                .cloned()
+                .unwrap()
+                .into_rust::<f32>()
+                .unwrap(),
+            6.0
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use super::*;
+use ron::value::RawValue;
 use serde::Deserialize;
-use std::{error::Error, fs};
+use std::{error::Error, fs, io, path::PathBuf};
 
 pub fn plugin(app: &mut App) {
     app.init_resource::<Settings>().init_resource::<ActiveTab>();
@@ -10,38 +11,216 @@ pub fn plugin(app: &mut App) {
     );
 }
 
-pub const SETTINGS_PATH: &str = "assets/settings.ron";
+/// Defaults shipped with the game, never written to
+pub const SETTINGS_DEFAULTS_PATH: &str = "assets/settings.ron";
+/// File name of the user settings in [`config_dir`]
+pub const SETTINGS_FILE: &str = "settings.ron";
 
+/// What [`Settings::read`] found. Nothing is logged while reading,
+/// the window settings are read in `main` before logging is set up.
+pub struct SettingsFile {
+    pub settings: Settings,
+    /// User settings, their backup or the shipped defaults
+    pub path: PathBuf,
+    /// Fields that failed to parse and were reset to defaults
+    pub reset: Vec<&'static str>,
+    pub warnings: Vec<String>,
+}
+
+/// Missing fields fall back to [`Settings::default`] one by one
 #[derive(Resource, Reflect, Deserialize, Serialize, Debug, Clone)]
 #[reflect(Resource)]
+#[serde(default)]
 pub struct Settings {
     // audio
     pub sound: Sound,
     // video
     pub fov: f32,
     pub sun_cycle: SunCycle,
+    pub graphics: Graphics,
+    pub display: DisplaySettings,
     // keybindings
     pub keybind: Keybind,
+    pub gamepad: GamepadBind,
+    /// Finished or skipped, "Play" goes straight to gameplay
+    pub tutorial_done: bool,
 }
 
 impl Settings {
     pub fn music(&self) -> Volume {
-        Volume::Linear(self.sound.general * self.sound.music)
+        self.music_faded(1.0)
+    }
+
+    /// Music volume scaled by a crossfade factor in `0..=1`
+    pub fn music_faded(&self, fade: f32) -> Volume {
+        Volume::Linear(self.sound.general * self.sound.music * fade)
     }
 
     pub fn sfx(&self) -> Volume {
-        Volume::Linear(self.sound.general * self.sound.sfx)
+        self.sfx_faded(1.0)
+    }
+
+    /// Sfx volume scaled by a crossfade factor in `0..=1`
+    pub fn sfx_faded(&self, fade: f32) -> Volume {
+        Volume::Linear(self.sound.general * self.sound.sfx * fade)
+    }
+
+    /// User settings in the platform config directory
+    pub fn path() -> PathBuf {
+        config_dir().join(SETTINGS_FILE)
+    }
+
+    /// Reads user settings, or the shipped defaults if the user has none yet.
+    ///
+    /// A file that fails to parse as a whole is replaced by the backup of the previous save,
+    /// without one it is an error naming the line and column.
+    /// Missing fields and fields of the wrong type are filled in from defaults.
+    pub fn read() -> Result<SettingsFile, Box<dyn Error>> {
+        let read = match read_with_backup(&Self::path(), Self::parse) {
+            Err(e)
+                if e.downcast_ref::<io::Error>().map(io::Error::kind)
+                    == Some(io::ErrorKind::NotFound) =>
+            {
+                let defaults = PathBuf::from(SETTINGS_DEFAULTS_PATH);
+                let content = fs::read_to_string(&defaults)?;
+                // ron errors start with `line:column`
+                let parsed =
+                    Self::parse(&content).map_err(|e| format!("{}:{e}", defaults.display()))?;
+                ReadFile {
+                    value: parsed,
+                    path: defaults,
+                    warning: None,
+                }
+            }
+            read => read?,
+        };
+        let (settings, reset, mut warnings) = read.value;
+        warnings.extend(read.warning);
+        Ok(SettingsFile {
+            settings,
+            path: read.path,
+            reset,
+            warnings,
+        })
     }
 
-    pub fn read() -> Result<Self, Box<dyn Error>> {
-        let content = fs::read_to_string(SETTINGS_PATH)?;
-        let settings = ron::from_str(&content).unwrap_or_default();
-        Ok(settings)
+    /// Parses every field on its own, a mistyped one is reset without the rest
+    fn parse(content: &str) -> Result<(Self, Vec<&'static str>, Vec<String>), Box<dyn Error>> {
+        let raw: RawSettings = ron::Options::default()
+            .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+            .from_str(content)?;
+        let mut settings = Self::default();
+        let mut reset = vec![];
+        let mut warnings = vec![];
+        macro_rules! fields {
+            ($($field:ident),*) => {$(
+                if let Some(value) = raw.$field {
+                    match value.into_rust() {
+                        Ok(value) => settings.$field = value,
+                        Err(e) => {
+                            let field = stringify!($field);
+                            warnings.push(format!(
+                                "settings: {field} is invalid, resetting to default: {e}"
+                            ));
+                            reset.push(field);
+                        }
+                    }
+                }
+            )*};
+        }
+        fields!(
+            sound,
+            fov,
+            sun_cycle,
+            graphics,
+            display,
+            keybind,
+            gamepad,
+            tutorial_done
+        );
+        Ok((settings, reset, warnings))
+    }
+
+    /// Resets values outside of the limits in `config.ron` to their defaults,
+    /// returns the names of the reset fields
+    pub fn validate(&mut self, limits: &SettingsPreloaded) -> Vec<&'static str> {
+        let defaults = Self::default();
+        let volume = limits.min_volume..=limits.max_volume;
+        let fov = limits.min_fov..=limits.max_fov;
+        let shadow_distance = Graphics::SHADOW_DISTANCE;
+        let render_scale = Graphics::RENDER_SCALE;
+        let deadzone = GamepadBind::MIN_DEADZONE..=GamepadBind::MAX_DEADZONE;
+        let sensitivity = GamepadBind::MIN_SENSITIVITY..=GamepadBind::MAX_SENSITIVITY;
+        let fields = [
+            (
+                "sound.general",
+                &mut self.sound.general,
+                defaults.sound.general,
+                &volume,
+            ),
+            (
+                "sound.music",
+                &mut self.sound.music,
+                defaults.sound.music,
+                &volume,
+            ),
+            (
+                "sound.sfx",
+                &mut self.sound.sfx,
+                defaults.sound.sfx,
+                &volume,
+            ),
+            ("fov", &mut self.fov, defaults.fov, &fov),
+            (
+                "graphics.shadow_distance",
+                &mut self.graphics.shadow_distance,
+                defaults.graphics.shadow_distance,
+                &shadow_distance,
+            ),
+            (
+                "graphics.render_scale",
+                &mut self.graphics.render_scale,
+                defaults.graphics.render_scale,
+                &render_scale,
+            ),
+            (
+                "gamepad.deadzone",
+                &mut self.gamepad.deadzone,
+                defaults.gamepad.deadzone,
+                &deadzone,
+            ),
+            (
+                "gamepad.sensitivity",
+                &mut self.gamepad.sensitivity,
+                defaults.gamepad.sensitivity,
+                &sensitivity,
+            ),
+        ];
+
+        let mut reset = vec![];
+        for (name, value, default, range) in fields {
+            if !range.contains(value) {
+                warn!("settings: {name} = {value} is outside of {range:?}, resetting to {default}");
+                *value = default;
+                reset.push(name);
+            }
+        }
+        if !Graphics::CASCADES.contains(&self.graphics.cascades) {
+            warn!(
+                "settings: graphics.cascades = {} is outside of {:?}, resetting to {}",
+                self.graphics.cascades,
+                Graphics::CASCADES,
+                defaults.graphics.cascades
+            );
+            self.graphics.cascades = defaults.graphics.cascades;
+            reset.push("graphics.cascades");
+        }
+        reset
     }
 
     pub fn save(&self) -> Result<(), Box<dyn Error>> {
         let content = ron::ser::to_string_pretty(self, Default::default())?;
-        fs::write(SETTINGS_PATH, content)?;
+        write_atomic(&Self::path(), &content)?;
         Ok(())
     }
 }
@@ -52,27 +231,58 @@ impl Default for Settings {
             sun_cycle: SunCycle::DayNight,
             sound: Sound::default(),
             fov: 45.0, // bevy default
+            graphics: Graphics::default(),
+            display: DisplaySettings::default(),
             keybind: Keybind::default(),
+            gamepad: GamepadBind::default(),
+            tutorial_done: false,
         }
     }
 }
 
 fn inject_settings_from_cfg(mut commands: Commands, cfg: Res<Config>) {
-    let settings = match Settings::read() {
-        Ok(settings) => {
-            info!("loaded settings from '{SETTINGS_PATH}'");
-            settings
+    let (mut settings, mut reset) = match Settings::read() {
+        Ok(read) => {
+            info!("loaded settings from '{}'", read.path.display());
+            for warning in read.warnings {
+                warn!("{warning}");
+            }
+            (read.settings, read.reset)
         }
         Err(e) => {
-            info!("unable to load settings from '{SETTINGS_PATH}', switching to defaults: {e}");
-            Default::default()
+            error!("unable to load settings, switching to defaults: {e}");
+            commands.trigger(OnNotify(format!("Settings were reset to defaults\n{e}")));
+            let settings = Settings {
+                sound: cfg.sound.clone(),
+                ..default()
+            };
+            (settings, vec![])
         }
     };
 
-    commands.insert_resource(Settings {
-        sound: cfg.sound.clone(),
-        ..settings
-    });
+    reset.extend(settings.validate(&cfg.settings));
+    if !reset.is_empty() {
+        commands.trigger(OnNotify(format!(
+            "Settings were partially reset: {}",
+            reset.join(", ")
+        )));
+    }
+
+    commands.insert_resource(settings);
+}
+
+/// [`Settings`] fields as written, parsed one by one in [`Settings::parse`]
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RawSettings {
+    sound: Option<Box<RawValue>>,
+    fov: Option<Box<RawValue>>,
+    sun_cycle: Option<Box<RawValue>>,
+    graphics: Option<Box<RawValue>>,
+    display: Option<Box<RawValue>>,
+    keybind: Option<Box<RawValue>>,
+    gamepad: Option<Box<RawValue>>,
+    tutorial_done: Option<Box<RawValue>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Component)]
@@ -82,6 +292,7 @@ pub enum UiTab {
     Audio,
     Video,
     Keybindings,
+    Gamepad,
 }
 
 #[derive(Resource, Default)]
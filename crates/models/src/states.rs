@@ -1,7 +1,10 @@
 use super::*;
+use std::{error::Error, path::PathBuf};
 
 pub fn plugin(app: &mut App) {
-    app.init_resource::<GameState>();
+    app.init_resource::<GameState>()
+        .init_resource::<Lobby>()
+        .add_systems(Startup, load_high_score);
 }
 
 #[derive(Resource, Reflect, Debug, Clone)]
@@ -16,6 +19,7 @@ pub struct GameState {
     pub debug_ui: bool,
     pub paused: bool,
     pub muted: bool,
+    pub score: Score,
 }
 
 impl Default for GameState {
@@ -27,6 +31,7 @@ impl Default for GameState {
             debug_ui: false,
             paused: false,
             muted: false,
+            score: Score::default(),
         }
     }
 }
@@ -36,5 +41,96 @@ impl GameState {
         self.modals.clear();
         self.paused = false;
         self.muted = false;
+        self.score.run = 0;
+    }
+}
+
+/// Collectible points of the current run, the best run is persisted in [`Score::path`]
+#[derive(Reflect, Serialize, Deserialize, Default, Debug, Clone, Copy)]
+pub struct Score {
+    #[serde(skip)]
+    pub run: i32,
+    pub high: i32,
+}
+
+impl Score {
+    /// Returns `true` if the run beat the high score
+    pub fn add(&mut self, points: i32) -> bool {
+        self.run += points;
+        if self.run > self.high {
+            self.high = self.run;
+            return true;
+        }
+        false
+    }
+
+    /// High score in the platform data directory
+    pub fn path() -> PathBuf {
+        data_dir().join("highscore.ron")
+    }
+
+    pub fn read() -> Result<Self, Box<dyn Error>> {
+        read_with_backup(&Self::path(), |content| Ok(ron::from_str(content)?)).map(ReadFile::logged)
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let content = ron::ser::to_string_pretty(self, Default::default())?;
+        write_atomic(&Self::path(), &content)?;
+        Ok(())
+    }
+}
+
+fn load_high_score(mut state: ResMut<GameState>) {
+    match Score::read() {
+        Ok(score) => state.score.high = score.high,
+        Err(e) => info!("no high score at '{}': {e}", Score::path().display()),
+    }
+}
+
+/// Gamepads that joined a local co-op session, in join order.
+/// Empty lobby means single player.
+#[derive(Resource, Reflect, Debug, Clone, Default)]
+#[reflect(Resource)]
+pub struct Lobby {
+    pub gamepads: Vec<Entity>,
+}
+
+impl Lobby {
+    pub const MAX_PLAYERS: usize = 4;
+
+    pub fn is_coop(&self) -> bool {
+        !self.gamepads.is_empty()
+    }
+
+    pub fn join(&mut self, gamepad: Entity) -> bool {
+        if self.gamepads.len() >= Self::MAX_PLAYERS || self.gamepads.contains(&gamepad) {
+            return false;
+        }
+        self.gamepads.push(gamepad);
+        true
+    }
+
+    pub fn leave(&mut self, gamepad: Entity) -> bool {
+        let len = self.gamepads.len();
+        self.gamepads.retain(|g| *g != gamepad);
+        len != self.gamepads.len()
+    }
+
+    /// Input slots to spawn players for: one per joined gamepad, or a single
+    /// keyboard + any gamepad slot when nobody joined.
+    pub fn slots(&self) -> Vec<GameplayCtx> {
+        if self.gamepads.is_empty() {
+            return vec![GameplayCtx::default()];
+        }
+        self.gamepads
+            .iter()
+            .enumerate()
+            .map(|(slot, gamepad)| GameplayCtx::new(slot, Some(*gamepad)))
+            .collect()
+    }
+
+    /// Number of split screen viewports
+    pub fn players(&self) -> usize {
+        self.gamepads.len().max(1)
     }
 }
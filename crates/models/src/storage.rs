@@ -0,0 +1,147 @@
+//! Where user files live and how they are written.
+//!
+//! Settings go to the platform config directory, saves and the high score to the
+//! data directory (`$XDG_CONFIG_HOME`/`$XDG_DATA_HOME` on Linux). Platforms without
+//! one (wasm) fall back to `assets/`, read only so the shipped defaults stay as they are.
+
+use bevy::log::warn;
+use std::{
+    error::Error,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Directory name under the platform config/data directories
+pub const APP_DIR: &str = "bevy_new_third_person";
+/// Read only stand-in for platforms without config/data directories
+pub const FALLBACK_DIR: &str = "assets";
+
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir()
+        .map(|dir| dir.join(APP_DIR))
+        .unwrap_or_else(|| PathBuf::from(FALLBACK_DIR))
+}
+
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir()
+        .map(|dir| dir.join(APP_DIR))
+        .unwrap_or_else(|| PathBuf::from(FALLBACK_DIR))
+}
+
+/// Writes `content` next to `path` first and renames it over, so a crash mid-write
+/// leaves either the old or the new file. The previous file is kept as `<path>.bak`
+/// for [`read_with_backup`]. Refuses to write into [`FALLBACK_DIR`].
+pub fn write_atomic(path: &Path, content: &str) -> io::Result<()> {
+    if path.starts_with(FALLBACK_DIR) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "no user directory on this platform, not overwriting '{}'",
+                path.display()
+            ),
+        ));
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let tmp = with_suffix(path, "tmp");
+    let mut file = fs::File::create(&tmp)?;
+    file.write_all(content.as_bytes())?;
+    file.sync_all()?;
+
+    if path.exists() {
+        fs::copy(path, with_suffix(path, "bak"))?;
+    }
+    fs::rename(&tmp, path)
+}
+
+/// A file parsed by [`read_with_backup`]
+pub struct ReadFile<T> {
+    pub value: T,
+    /// The file itself or its backup
+    pub path: PathBuf,
+    /// Why the backup was read instead. Not logged while reading,
+    /// settings are read before logging is set up.
+    pub warning: Option<String>,
+}
+
+impl<T> ReadFile<T> {
+    /// Logs why the backup was read, if it was
+    pub fn logged(self) -> T {
+        if let Some(warning) = &self.warning {
+            warn!("{warning}");
+        }
+        self.value
+    }
+}
+
+/// Parses `path`, or the `<path>.bak` left by [`write_atomic`] if that fails.
+/// Errors are the ones of `path`: [`io::Error`]s as they are, `parse` errors prefixed with the path.
+pub fn read_with_backup<T>(
+    path: &Path,
+    parse: impl Fn(&str) -> Result<T, Box<dyn Error>>,
+) -> Result<ReadFile<T>, Box<dyn Error>> {
+    let read = |path: &Path| -> Result<T, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        parse(&content).map_err(|e| format!("{}:{e}", path.display()).into())
+    };
+    match read(path) {
+        Ok(value) => Ok(ReadFile {
+            value,
+            path: path.to_path_buf(),
+            warning: None,
+        }),
+        Err(e) => {
+            let backup = with_suffix(path, "bak");
+            match read(&backup) {
+                Ok(value) => Ok(ReadFile {
+                    value,
+                    warning: Some(format!("{e}, read '{}' instead", backup.display())),
+                    path: backup,
+                }),
+                Err(_) => Err(e),
+            }
+        }
+    }
+}
+
+/// `settings.ron` -> `settings.ron.<suffix>`
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fallback_dir_is_read_only() {
+        let path = Path::new(FALLBACK_DIR).join("settings.ron");
+        let err = write_atomic(&path, "()").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+    }
+
+    #[test]
+    fn broken_file_falls_back_to_backup() {
+        let dir = std::env::temp_dir().join(format!("storage_test_{}", std::process::id()));
+        let path = dir.join("highscore.ron");
+        write_atomic(&path, "(high: 3)").unwrap();
+        write_atomic(&path, "(high: ").unwrap();
+
+        let parse =
+            |content: &str| -> Result<ron::Value, Box<dyn Error>> { Ok(ron::from_str(content)?) };
+        let read = read_with_backup(&path, parse).unwrap();
+        assert_eq!(read.path, with_suffix(&path, "bak"));
+        assert!(read.warning.is_some());
+        assert_eq!(
+            read.value,
+            ron::from_str::<ron::Value>("(high: 3)").unwrap()
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,70 @@
+use super::*;
+use bevy_seedling::sample::Sample;
+
+/// What the ground is made of, picks the footstep sounds.
+/// Authored in Blender through Skein on colliders or any of their ancestors,
+/// ground without one, or a surface without clips, sounds like [`SurfaceMaterial::Stone`].
+#[derive(
+    Component, Reflect, Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq, Hash,
+)]
+#[reflect(Component, Default)]
+pub enum SurfaceMaterial {
+    #[default]
+    Stone,
+    Snow,
+    Grass,
+}
+
+impl SurfaceMaterial {
+    pub const ALL: [Self; 3] = [Self::Stone, Self::Snow, Self::Grass];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Stone => "stone",
+            Self::Snow => "snow",
+            Self::Grass => "grass",
+        }
+    }
+}
+
+/// Kind of footstep, each surface has its own clips for every kind
+#[derive(Reflect, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StepKind {
+    #[default]
+    Walk,
+    Crouch,
+    Sprint,
+    Land,
+}
+
+impl StepKind {
+    pub const ALL: [Self; 4] = [Self::Walk, Self::Crouch, Self::Sprint, Self::Land];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Walk => "walk",
+            Self::Crouch => "crouch",
+            Self::Sprint => "sprint",
+            Self::Land => "land",
+        }
+    }
+
+    /// Volume relative to the spatial sfx pool
+    pub fn volume(&self) -> f32 {
+        match self {
+            Self::Walk => 1.0,
+            Self::Crouch => 0.5,
+            Self::Sprint => 1.2,
+            Self::Land => 1.4,
+        }
+    }
+}
+
+/// Footstep state of a character
+#[derive(Component, Default, Debug)]
+pub struct Footsteps {
+    /// Last clip played, skipped by the next step so it doesn't repeat
+    pub last: Option<AssetId<Sample>>,
+    /// Was in the air last frame, to detect landing
+    pub airborne: bool,
+}
@@ -0,0 +1,151 @@
+use super::*;
+
+/// Clock of the scene lighting, advanced during gameplay unless paused.
+/// Sun and moon positions, light and exposure are all derived from the hour.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub struct TimeOfDay {
+    /// In `0.0..24.0`, 6 is sunrise and 18 sunset
+    pub hour: f32,
+    pub paused: bool,
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self {
+            hour: 12.0,
+            paused: false,
+        }
+    }
+}
+
+impl TimeOfDay {
+    pub fn set(&mut self, hour: f32) {
+        self.hour = hour.rem_euclid(24.0);
+    }
+
+    /// Moves the clock on by `secs` real seconds of a day lasting `day_length` seconds
+    pub fn advance(&mut self, secs: f32, day_length: f32) {
+        if !self.paused {
+            self.set(self.hour + secs * 24.0 / day_length.max(f32::EPSILON));
+        }
+    }
+
+    /// `HH:MM`, for the console and UI
+    pub fn clock(&self) -> String {
+        let minutes = (self.hour * 60.0) as u32;
+        format!("{:02}:{:02}", minutes / 60 % 24, minutes % 60)
+    }
+}
+
+/// Keyframes of `(hour, value)` sorted by hour, interpolated linearly
+/// and wrapping around midnight.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
+pub struct DayCurve(pub Vec<(f32, f32)>);
+
+impl DayCurve {
+    pub fn sample(&self, hour: f32) -> f32 {
+        let keys = &self.0;
+        let (Some(first), Some(last)) = (keys.first(), keys.last()) else {
+            return 0.0;
+        };
+        let hour = hour.rem_euclid(24.0);
+        let next = keys.iter().position(|(h, _)| *h > hour);
+        let (from, to) = match next {
+            Some(0) | None => (*last, (first.0 + 24.0, first.1)),
+            Some(i) => (keys[i - 1], keys[i]),
+        };
+        // before the first key, the segment from the last one started yesterday
+        let hour = if hour < from.0 { hour + 24.0 } else { hour };
+        let span = to.0 - from.0;
+        if span <= f32::EPSILON {
+            return to.1;
+        }
+        from.1 + (to.1 - from.1) * (hour - from.0) / span
+    }
+}
+
+/// `time_of_day` section of `config.ron`
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
+pub struct DayConfig {
+    /// Real seconds a full day takes
+    pub day_length: f32,
+    /// Hour a new game starts at
+    pub start_hour: f32,
+    /// Lux
+    pub sun_illuminance: DayCurve,
+    pub moon_illuminance: DayCurve,
+    /// Kelvin
+    pub sun_temperature: DayCurve,
+    /// Camera EV100, lower is brighter
+    pub exposure: DayCurve,
+    pub ambient_brightness: DayCurve,
+    /// Share of light the ground bounces back into the sky, `Atmosphere::EARTH` has 0.3
+    pub ground_albedo: DayCurve,
+}
+
+/// Approximate color of a black body at `kelvin`, good enough for 1000K to 40000K
+pub fn color_temperature(kelvin: f32) -> Color {
+    let t = kelvin.clamp(1000.0, 40000.0) / 100.0;
+    let red = if t <= 66.0 {
+        255.0
+    } else {
+        329.699 * (t - 60.0).powf(-0.133_204_76)
+    };
+    let green = if t <= 66.0 {
+        99.470_8 * t.ln() - 161.119_57
+    } else {
+        288.122_16 * (t - 60.0).powf(-0.075_514_85)
+    };
+    let blue = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        138.517_73 * (t - 10.0).ln() - 305.044_8
+    };
+    Color::srgb(
+        red.clamp(0.0, 255.0) / 255.0,
+        green.clamp(0.0, 255.0) / 255.0,
+        blue.clamp(0.0, 255.0) / 255.0,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn samples_between_keys() {
+        let curve = DayCurve(vec![(6.0, 0.0), (12.0, 1.0), (18.0, 0.0)]);
+        assert!(close(curve.sample(6.0), 0.0));
+        assert!(close(curve.sample(9.0), 0.5));
+        assert!(close(curve.sample(12.0), 1.0));
+        assert!(close(curve.sample(15.0), 0.5));
+    }
+
+    #[test]
+    fn wraps_across_midnight() {
+        // 20:00 -> 4:00 spans 8 hours over midnight
+        let curve = DayCurve(vec![(4.0, 0.0), (20.0, 1.0)]);
+        assert!(close(curve.sample(20.0), 1.0));
+        assert!(close(curve.sample(22.0), 0.75));
+        assert!(close(curve.sample(0.0), 0.5));
+        assert!(close(curve.sample(2.0), 0.25));
+        assert!(close(curve.sample(4.0), 0.0));
+        assert!(close(curve.sample(-2.0), curve.sample(22.0)));
+        assert!(close(curve.sample(26.0), curve.sample(2.0)));
+    }
+
+    #[test]
+    fn degenerate_curves() {
+        assert_eq!(DayCurve(vec![]).sample(12.0), 0.0);
+        let single = DayCurve(vec![(8.0, 0.3)]);
+        assert!(close(single.sample(3.0), 0.3));
+        assert!(close(single.sample(20.0), 0.3));
+    }
+}
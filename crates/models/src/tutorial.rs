@@ -0,0 +1,67 @@
+use super::*;
+
+/// Tutorial steps in order, loaded from `tutorial.ron`
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect, Asset, Resource)]
+#[reflect(Resource)]
+pub struct TutorialSteps {
+    pub steps: Vec<TutorialStep>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
+pub struct TutorialStep {
+    /// Completes the step once fired
+    pub action: TutorialAction,
+    /// `{binding}` is replaced with the current keyboard binding of the action
+    pub prompt: String,
+}
+
+impl TutorialStep {
+    pub fn prompt(&self, keybind: &Keybind) -> String {
+        self.prompt
+            .replace("{binding}", &self.action.binding(keybind))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Reflect)]
+pub enum TutorialAction {
+    Move,
+    Jump,
+    Sprint,
+    Dash,
+    Crouch,
+    Attack,
+}
+
+impl TutorialAction {
+    /// Current binding as shown in prompts, e.g. "W A S D" or "Space"
+    pub fn binding(&self, keybind: &Keybind) -> String {
+        let first = |inputs: &[Input]| {
+            inputs
+                .first()
+                .map(|input| input.to_string())
+                .unwrap_or_else(|| "unbound".to_string())
+        };
+        match self {
+            TutorialAction::Move => [
+                &keybind.forward,
+                &keybind.left,
+                &keybind.backward,
+                &keybind.right,
+            ]
+            .map(|inputs| first(inputs))
+            .join(" "),
+            TutorialAction::Jump => first(&keybind.jump),
+            TutorialAction::Sprint => first(&keybind.sprint),
+            TutorialAction::Dash => first(&keybind.dash),
+            TutorialAction::Crouch => first(&keybind.crouch),
+            TutorialAction::Attack => first(&keybind.attack),
+        }
+    }
+}
+
+/// Present while the tutorial runs in [`Screen::Gameplay`]
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct TutorialProgress {
+    pub step: usize,
+}
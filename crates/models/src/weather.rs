@@ -0,0 +1,141 @@
+use super::*;
+use avian3d::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Reflect, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weather {
+    #[default]
+    Clear,
+    Overcast,
+    Fog,
+    Rain,
+    Snow,
+}
+
+impl Weather {
+    pub const ALL: [Weather; 5] = [
+        Weather::Clear,
+        Weather::Overcast,
+        Weather::Fog,
+        Weather::Rain,
+        Weather::Snow,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Weather::Clear => "clear",
+            Weather::Overcast => "overcast",
+            Weather::Fog => "fog",
+            Weather::Rain => "rain",
+            Weather::Snow => "snow",
+        }
+    }
+}
+
+/// Scene volume forcing its weather while a player is inside,
+/// authored in Blender through Skein together with its collider.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component, Default)]
+#[require(Sensor, CollisionEventsEnabled)]
+pub struct WeatherZone {
+    pub weather: Weather,
+}
+
+/// Weather presets and the schedule going through them, loaded from `weather.ron`
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect, Asset, Resource)]
+#[reflect(Resource)]
+pub struct WeatherSet {
+    /// Seconds to blend from one weather into the next
+    pub transition: f32,
+    /// `(weather, seconds)` in order, looped during gameplay
+    pub schedule: Vec<(Weather, f32)>,
+    pub presets: HashMap<Weather, WeatherPreset>,
+}
+
+impl WeatherSet {
+    pub fn preset(&self, weather: Weather) -> WeatherPreset {
+        self.presets.get(&weather).cloned().unwrap_or_else(|| {
+            warn!("no {weather:?} preset in weather.ron");
+            WeatherPreset::default()
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect, PartialEq)]
+#[serde(default)]
+pub struct WeatherPreset {
+    /// Distance in world units objects stay visible through the fog
+    pub fog_visibility: f32,
+    /// sRGB
+    pub fog_color: (f32, f32, f32),
+    /// Scales the sun and moon illuminance of the time of day
+    pub light: f32,
+    /// Scales `Atmosphere::EARTH` rayleigh scattering, the blue of the sky
+    pub rayleigh: f32,
+    /// Scales `Atmosphere::EARTH` mie scattering and absorption, the haze around the sun
+    pub mie: f32,
+    pub particles: Option<Particles>,
+    /// Sound looping while the weather lasts
+    pub ambient: Option<String>,
+}
+
+impl Default for WeatherPreset {
+    fn default() -> Self {
+        Self {
+            fog_visibility: 500.0,
+            fog_color: (0.35, 0.48, 0.66),
+            light: 1.0,
+            rayleigh: 1.0,
+            mie: 1.0,
+            particles: None,
+            ambient: None,
+        }
+    }
+}
+
+impl WeatherPreset {
+    /// Blends the continuous values, particles and ambient sound switch over halfway
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        Self {
+            fog_visibility: lerp(self.fog_visibility, other.fog_visibility),
+            fog_color: (
+                lerp(self.fog_color.0, other.fog_color.0),
+                lerp(self.fog_color.1, other.fog_color.1),
+                lerp(self.fog_color.2, other.fog_color.2),
+            ),
+            light: lerp(self.light, other.light),
+            rayleigh: lerp(self.rayleigh, other.rayleigh),
+            mie: lerp(self.mie, other.mie),
+            particles: if t < 0.5 {
+                self.particles.clone()
+            } else {
+                other.particles.clone()
+            },
+            ambient: if t < 0.5 {
+                self.ambient.clone()
+            } else {
+                other.ambient.clone()
+            },
+        }
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParticleKind {
+    /// Falling streaks
+    Rain,
+    /// Swaying flakes
+    Snow,
+}
+
+/// Particles falling in a box around the camera
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect, PartialEq)]
+pub struct Particles {
+    pub kind: ParticleKind,
+    pub count: usize,
+    /// World units per second
+    pub fall_speed: f32,
+    /// Half extents of the box around the camera
+    pub area: (f32, f32, f32),
+}
@@ -3,38 +3,90 @@ use bevy_tnua::{TnuaAnimatingState, TnuaAnimatingStateDirective, builtins::*};
 
 const ANIMATION_FACTOR: f32 = 0.1;
 
+/// Observes the character model [`SceneRoot`] spawned as a child of the [`Player`]
 pub fn prepare_animations(
-    _: Trigger<SceneInstanceReady>,
+    on: Trigger<SceneInstanceReady>,
     models: Res<Models>,
+    set: Res<AnimationSet>,
     gltf_assets: Res<Assets<Gltf>>,
-    animation_player: Query<Entity, With<AnimationPlayer>>,
-    mut player: Query<&mut Player>,
+    children: Query<&Children>,
+    parents: Query<&ChildOf>,
+    animation_players: Query<Entity, With<AnimationPlayer>>,
+    mut players: Query<&mut Player>,
     mut commands: Commands,
     mut animation_graphs: ResMut<Assets<AnimationGraph>>,
+    mut animation_clips: ResMut<Assets<AnimationClip>>,
 ) {
     let Some(gltf) = gltf_assets.get(&models.player) else {
         return;
     };
-    let Ok(animation_player) = animation_player.single() else {
+    let scene = on.target();
+    let Some(animation_player) = children
+        .iter_descendants(scene)
+        .find(|e| animation_players.contains(*e))
+    else {
         return;
     };
-    let Ok(mut player) = player.single_mut() else {
+    let Ok(&ChildOf(player_entity)) = parents.get(scene) else {
+        return;
+    };
+    let Ok(mut player) = players.get_mut(player_entity) else {
         return;
     };
 
     let mut graph = AnimationGraph::new();
     let root_node = graph.root;
 
-    // Create flat animation graph
+    // Every clip of the model sits directly under the root, crossfaded by weight
     for (name, clip) in gltf.named_animations.iter() {
         let node_index = graph.add_clip(clip.clone(), 1.0, root_node);
         player.animations.insert(name.to_string(), node_index);
     }
 
-    // TODO: check if it still works on the second gamepad
+    // Idle/jog/sprint get their own blend node, weighted by ground speed in `blend_locomotion`.
+    // The node weight is what gets crossfaded against the other states.
+    let clip = |entry: &AnimationEntry| {
+        gltf.named_animations
+            .get(entry.clip.as_str())
+            .cloned()
+            .unwrap_or_default()
+    };
+    let node = graph.add_blend(0.0, root_node);
+    let locomotion = Locomotion {
+        node,
+        idle: graph.add_clip(clip(&set.stand_idle), 1.0, node),
+        jog: graph.add_clip(clip(&set.run), 1.0, node),
+        sprint: graph.add_clip(clip(&set.sprint), 1.0, node),
+    };
+
+    // Strike clips are copied per player to carry the hitbox events, a missing clip
+    // still gets a node so that combo steps keep their indices
+    player.attacks = set
+        .attacks
+        .iter()
+        .map(|attack| {
+            let mut clip = gltf
+                .named_animations
+                .get(attack.animation.clip.as_str())
+                .and_then(|handle| animation_clips.get(handle))
+                .cloned()
+                .unwrap_or_default();
+            let (start, end) = attack.hit;
+            clip.set_duration(clip.duration().max(end));
+            clip.add_event(start, HitboxOn);
+            clip.add_event(end, HitboxOff);
+            graph.add_clip(animation_clips.add(clip), 1.0, root_node)
+        })
+        .collect();
+
+    commands.entity(animation_player).insert((
+        AnimationGraphHandle(animation_graphs.add(graph)),
+        locomotion,
+        Crossfade::default(),
+    ));
     commands
-        .entity(animation_player)
-        .insert(AnimationGraphHandle(animation_graphs.add(graph)));
+        .entity(player_entity)
+        .insert(PlayerAnimator(animation_player));
 }
 
 /// Tnua takes the heavy lifting with blending animations, but it leads to most of the animation
@@ -46,118 +98,178 @@ pub fn prepare_animations(
 /// all tnua related stuff and it should still work
 pub fn animating(
     cfg: Res<Config>,
+    time: Res<Time>,
+    set: Res<AnimationSet>,
     mut player_q: Query<(
         &TnuaController,
+        &PlayerAnimator,
         &mut Player,
         &mut TnuaAnimatingState<AnimationState>,
+        Option<&Combo>,
+        Has<HitStun>,
+    )>,
+    mut animation_players: Query<(
+        &mut AnimationPlayer,
+        &mut Crossfade,
+        &Locomotion,
+        &AnimationGraphHandle,
     )>,
-    mut animation_player: Query<&mut AnimationPlayer>,
+    mut animation_graphs: ResMut<Assets<AnimationGraph>>,
 ) {
-    // An actual game should match the animation player and the controller. Here we cheat for
-    // simplicity and use the only controller and only player.
-    let Ok((controller, mut player, mut animating_state)) = player_q.single_mut() else {
-        return;
-    };
-    let Ok(mut animation_player) = animation_player.single_mut() else {
-        return;
-    };
+    for (controller, animator, mut player, mut animating_state, combo, stunned) in
+        player_q.iter_mut()
+    {
+        let Ok((mut animation_player, mut crossfade, locomotion, graph)) =
+            animation_players.get_mut(animator.0)
+        else {
+            continue;
+        };
+        animate(
+            &cfg,
+            &set,
+            controller,
+            &mut player,
+            &mut animating_state,
+            combo,
+            stunned,
+            &mut animation_player,
+            &mut crossfade,
+            locomotion,
+        );
+        if let Some((_, basis_state)) = controller.concrete_basis::<TnuaBuiltinWalk>() {
+            let speed = basis_state.running_velocity.length();
+            blend_locomotion(&cfg, &set, speed, locomotion, &mut animation_player);
+        }
+        apply_crossfade(
+            time.delta_secs(),
+            &mut crossfade,
+            locomotion,
+            &mut animation_player,
+            graph,
+            &mut animation_graphs,
+        );
+    }
+}
 
+#[allow(clippy::too_many_arguments)]
+fn animate(
+    cfg: &Config,
+    set: &AnimationSet,
+    controller: &TnuaController,
+    player: &mut Player,
+    animating_state: &mut TnuaAnimatingState<AnimationState>,
+    combo: Option<&Combo>,
+    stunned: bool,
+    animation_player: &mut AnimationPlayer,
+    crossfade: &mut Crossfade,
+    locomotion: &Locomotion,
+) {
     // First check Tnua animation directive
     // Here we use the data from TnuaController to determine what the character is currently doing,
     // so that we can later use that information to decide which animation to play.
     // First we look at the `action_name` to determine which action (if at all) the character is currently performing:
-    let current_animation = match controller.action_name() {
-        Some(TnuaBuiltinKnockback::NAME) => {
-            let (_, knockback_state) = controller
-                .concrete_action::<TnuaBuiltinKnockback>()
-                .expect("action name mismatch: Knockback");
-            match knockback_state {
-                TnuaBuiltinKnockbackState::Shove => AnimationState::KnockBack,
-                TnuaBuiltinKnockbackState::Pushback { .. } => AnimationState::KnockBack,
+    // Combat overrides whatever Tnua is doing, hit-stun first
+    let current_animation = if stunned {
+        AnimationState::KnockBack
+    } else if let Some(combo) = combo.filter(|combo| combo.attacking) {
+        AnimationState::Attack(combo.step)
+    } else {
+        match controller.action_name() {
+            Some(TnuaBuiltinKnockback::NAME) => {
+                let (_, knockback_state) = controller
+                    .concrete_action::<TnuaBuiltinKnockback>()
+                    .expect("action name mismatch: Knockback");
+                match knockback_state {
+                    TnuaBuiltinKnockbackState::Shove => AnimationState::KnockBack,
+                    TnuaBuiltinKnockbackState::Pushback { .. } => AnimationState::KnockBack,
+                }
             }
-        }
-        Some(TnuaBuiltinCrouch::NAME) => {
-            let (_, crouch_state) = controller
-                .concrete_action::<TnuaBuiltinCrouch>()
-                .expect("action name mismatch: Crouch");
-            // In case of crouch, we need the state of the basis to determine - based on
-            // the speed - if the charcter is just crouching or also crawling.
-            let Some((_, basis_state)) = controller.concrete_basis::<TnuaBuiltinWalk>() else {
-                return;
-            };
-            let basis_speed = basis_state.running_velocity.length();
-            let speed = Some(basis_speed)
-                .filter(|speed| cfg.player.movement.idle_to_run_threshold < *speed);
-            let is_crouching = basis_state.standing_offset.y < 0.05;
-            // info!(
-            //     "CROUCH: {is_crouching} speed: {basis_speed}, state:{crouch_state:?}, standing_offset: {}",
-            //     basis_state.standing_offset.y
-            // );
-            match (speed, is_crouching) {
-                (None, false) => AnimationState::StandIdle,
-                (None, true) => match crouch_state {
-                    TnuaBuiltinCrouchState::Maintaining => AnimationState::Crouch,
-                    // TODO: have rise animation
-                    TnuaBuiltinCrouchState::Rising => AnimationState::Crouch,
-                    // TODO: sink animation
-                    TnuaBuiltinCrouchState::Sinking => AnimationState::Crouch,
-                },
-                (Some(speed), false) => AnimationState::Run(ANIMATION_FACTOR * speed),
-                // TODO: place to handle slide here
-                (Some(speed), true) => AnimationState::Crawl(ANIMATION_FACTOR * speed * 4.0),
+            Some(TnuaBuiltinCrouch::NAME) => {
+                let (_, crouch_state) = controller
+                    .concrete_action::<TnuaBuiltinCrouch>()
+                    .expect("action name mismatch: Crouch");
+                // In case of crouch, we need the state of the basis to determine - based on
+                // the speed - if the charcter is just crouching or also crawling.
+                let Some((_, basis_state)) = controller.concrete_basis::<TnuaBuiltinWalk>() else {
+                    return;
+                };
+                let basis_speed = basis_state.running_velocity.length();
+                let speed = Some(basis_speed)
+                    .filter(|speed| cfg.player.movement.idle_to_run_threshold < *speed);
+                let is_crouching = basis_state.standing_offset.y < 0.05;
+                // info!(
+                //     "CROUCH: {is_crouching} speed: {basis_speed}, state:{crouch_state:?}, standing_offset: {}",
+                //     basis_state.standing_offset.y
+                // );
+                match (speed, is_crouching) {
+                    (None, false) => AnimationState::StandIdle,
+                    (None, true) => match crouch_state {
+                        TnuaBuiltinCrouchState::Maintaining => AnimationState::Crouch,
+                        // TODO: have rise animation
+                        TnuaBuiltinCrouchState::Rising => AnimationState::Crouch,
+                        // TODO: sink animation
+                        TnuaBuiltinCrouchState::Sinking => AnimationState::Crouch,
+                    },
+                    (Some(speed), false) => AnimationState::Run(ANIMATION_FACTOR * speed),
+                    // TODO: place to handle slide here
+                    (Some(speed), true) => AnimationState::Crawl(ANIMATION_FACTOR * speed * 4.0),
+                }
             }
-        }
-        // Unless you provide the action names yourself, prefer matching against the `NAME` const
-        // of the `TnuaAction` trait. Once `type_name` is stabilized as `const` Tnua will use it to
-        // generate these names automatically, which may result in a change to the name.
-        Some(TnuaBuiltinJump::NAME) => {
-            // In case of jump, we want to cast it so that we can get the concrete jump state.
-            let (_, jump_state) = controller
-                .concrete_action::<TnuaBuiltinJump>()
-                .expect("action name mismatch: Jump");
-            // Depending on the state of the jump, we need to decide if we want to play the jump
-            // animation or the fall animation.
-            match jump_state {
-                TnuaBuiltinJumpState::NoJump => return,
-                TnuaBuiltinJumpState::StartingJump { .. } => AnimationState::JumpStart,
-                TnuaBuiltinJumpState::SlowDownTooFastSlopeJump { .. } => AnimationState::JumpStart,
-                TnuaBuiltinJumpState::MaintainingJump { .. } => AnimationState::JumpLoop,
-                TnuaBuiltinJumpState::StoppedMaintainingJump => AnimationState::JumpLand,
-                TnuaBuiltinJumpState::FallSection => AnimationState::Fall,
+            // Unless you provide the action names yourself, prefer matching against the `NAME` const
+            // of the `TnuaAction` trait. Once `type_name` is stabilized as `const` Tnua will use it to
+            // generate these names automatically, which may result in a change to the name.
+            Some(TnuaBuiltinJump::NAME) => {
+                // In case of jump, we want to cast it so that we can get the concrete jump state.
+                let (_, jump_state) = controller
+                    .concrete_action::<TnuaBuiltinJump>()
+                    .expect("action name mismatch: Jump");
+                // Depending on the state of the jump, we need to decide if we want to play the jump
+                // animation or the fall animation.
+                match jump_state {
+                    TnuaBuiltinJumpState::NoJump => return,
+                    TnuaBuiltinJumpState::StartingJump { .. } => AnimationState::JumpStart,
+                    TnuaBuiltinJumpState::SlowDownTooFastSlopeJump { .. } => {
+                        AnimationState::JumpStart
+                    }
+                    TnuaBuiltinJumpState::MaintainingJump { .. } => AnimationState::JumpLoop,
+                    TnuaBuiltinJumpState::StoppedMaintainingJump => AnimationState::JumpLand,
+                    TnuaBuiltinJumpState::FallSection => AnimationState::Fall,
+                }
             }
-        }
-        Some(TnuaBuiltinClimb::NAME) => {
-            let Some((_, action_state)) = controller.concrete_action::<TnuaBuiltinClimb>() else {
-                return;
-            };
-            let TnuaBuiltinClimbState::Climbing { climbing_velocity } = action_state else {
-                return;
-            };
-            AnimationState::Climb(0.3 * climbing_velocity.dot(Vec3::Y))
-        }
-        // TODO: replace roll with actual dash
-        Some(TnuaBuiltinDash::NAME) => AnimationState::Dash,
-        Some(TnuaBuiltinWallSlide::NAME) => AnimationState::WallSlide,
-        Some("walljump") => AnimationState::WallJump,
-        Some(other) => panic!("Unknown action {other}"),
-        None => {
-            // If there is no action going on, we'll base the animation on the state of the basis.
-            let Some((_, basis_state)) = controller.concrete_basis::<TnuaBuiltinWalk>() else {
-                return;
-            };
-            if basis_state.standing_on_entity().is_none() {
-                AnimationState::Fall
-            } else {
-                let basis_speed = basis_state.running_velocity.length();
-                if basis_speed > cfg.player.movement.idle_to_run_threshold {
-                    let speed = ANIMATION_FACTOR * basis_speed;
-                    if basis_speed > cfg.player.movement.speed {
-                        AnimationState::Sprint(speed)
+            Some(TnuaBuiltinClimb::NAME) => {
+                let Some((_, action_state)) = controller.concrete_action::<TnuaBuiltinClimb>()
+                else {
+                    return;
+                };
+                let TnuaBuiltinClimbState::Climbing { climbing_velocity } = action_state else {
+                    return;
+                };
+                AnimationState::Climb(0.3 * climbing_velocity.dot(Vec3::Y))
+            }
+            // TODO: replace roll with actual dash
+            Some(TnuaBuiltinDash::NAME) => AnimationState::Dash,
+            Some(TnuaBuiltinWallSlide::NAME) => AnimationState::WallSlide,
+            Some("walljump") => AnimationState::WallJump,
+            Some(other) => panic!("Unknown action {other}"),
+            None => {
+                // If there is no action going on, we'll base the animation on the state of the basis.
+                let Some((_, basis_state)) = controller.concrete_basis::<TnuaBuiltinWalk>() else {
+                    return;
+                };
+                if basis_state.standing_on_entity().is_none() {
+                    AnimationState::Fall
+                } else {
+                    let basis_speed = basis_state.running_velocity.length();
+                    if basis_speed > cfg.player.movement.idle_to_run_threshold {
+                        let speed = ANIMATION_FACTOR * basis_speed;
+                        if basis_speed > cfg.player.movement.speed {
+                            AnimationState::Sprint(speed)
+                        } else {
+                            AnimationState::Run(speed)
+                        }
                     } else {
-                        AnimationState::Run(speed)
+                        AnimationState::StandIdle
                     }
-                } else {
-                    AnimationState::StandIdle
                 }
             }
         }
@@ -173,21 +285,32 @@ pub fn animating(
             // Some animation states have parameters, that we may want to use to control the
             // animation (without necessarily replacing it). In this case - control the speed
             // of the animation based on the speed of the movement.
-            AnimationState::Run(speed)
-            | AnimationState::Sprint(speed)
-            | AnimationState::Crawl(speed)
-            | AnimationState::Climb(speed) => {
-                for (_, active_animation) in animation_player.playing_animations_mut() {
-                    active_animation.set_speed(*speed);
+            // Run and sprint speeds are handled by `blend_locomotion`.
+            AnimationState::Crawl(speed) | AnimationState::Climb(speed) => {
+                let multiplier = set.get(state).speed;
+                if let Some(animation) = crossfade
+                    .target
+                    .and_then(|node| animation_player.animation_mut(node))
+                {
+                    animation.set_speed(multiplier * *speed);
                 }
             }
             // Jumping and dashing can be chained, we want to start a new jump/dash animation
             // when one jump/dash is chained to another.
             AnimationState::JumpStart | AnimationState::Dash => {
                 if controller.action_flow_status().just_starting().is_some() {
-                    animation_player.seek_all_by(0.0);
+                    if let Some(animation) = crossfade
+                        .target
+                        .and_then(|node| animation_player.animation_mut(node))
+                    {
+                        animation.replay();
+                    }
                 }
             }
+            // Chained strikes share the discriminant, fade into the next one
+            AnimationState::Attack(_) => {
+                play_state(state, set, player, locomotion, crossfade, animation_player);
+            }
             // For other animations we don't have anything special to do - so we just let them continue.
             _ => {}
         },
@@ -195,83 +318,144 @@ pub fn animating(
             old_state: _,
             state,
         } => {
-            // Here the animations are actually being ran with their respective speed
-            animation_player.stop_all();
-            match state {
-                AnimationState::StandIdle => {
-                    if let Some(index) = player.animations.get("Idle_Loop") {
-                        animation_player.start(*index).set_speed(1.0).repeat();
-                    }
-                }
-                AnimationState::Run(speed) => {
-                    if let Some(index) = player.animations.get("Jog_Fwd_Loop") {
-                        animation_player.start(*index).set_speed(*speed).repeat();
-                    }
-                }
-                AnimationState::Sprint(speed) => {
-                    if let Some(index) = player.animations.get("Sprint_Loop") {
-                        animation_player
-                            .start(*index)
-                            .set_speed(*speed * 3.0) // sprint animation to sprint factor ratio
-                            .repeat();
-                    }
-                }
-                AnimationState::JumpStart => {
-                    if let Some(index) = player.animations.get("Jump_Start") {
-                        animation_player.start(*index).set_speed(0.01);
-                    }
-                }
-                AnimationState::JumpLand => {
-                    if let Some(index) = player.animations.get("Jump_Land") {
-                        animation_player.start(*index).set_speed(0.01);
-                    }
-                }
-                AnimationState::JumpLoop => {
-                    if let Some(index) = player.animations.get("Jump_Loop") {
-                        animation_player.start(*index).set_speed(0.5).repeat();
-                    }
-                }
-                AnimationState::WallJump => {
-                    if let Some(index) = player.animations.get("Jump_Start") {
-                        animation_player.start(*index).set_speed(2.0);
-                    }
-                }
-                AnimationState::WallSlide => {
-                    if let Some(index) = player.animations.get("Jump_Loop") {
-                        animation_player.start(*index).set_speed(1.0).repeat();
-                    }
-                }
-                AnimationState::Fall => {
-                    if let Some(index) = player.animations.get("Jump_Loop") {
-                        animation_player.start(*index).set_speed(1.0).repeat();
-                    }
-                }
-                AnimationState::Crawl(speed) => {
-                    if let Some(index) = player.animations.get("Crouch_Fwd_Loop") {
-                        animation_player.start(*index).set_speed(*speed).repeat();
-                    }
-                }
-                AnimationState::Crouch => {
-                    if let Some(index) = player.animations.get("Crouch_Idle_Loop") {
-                        animation_player.start(*index).set_speed(1.0).repeat();
-                    }
-                }
-                AnimationState::Dash => {
-                    if let Some(index) = player.animations.get("Roll") {
-                        animation_player.start(*index).set_speed(3.0);
-                    }
-                }
-                AnimationState::KnockBack => {
-                    if let Some(index) = player.animations.get("Hit_Chest") {
-                        animation_player.start(*index).set_speed(1.0);
-                    }
-                }
-                AnimationState::Climb(speed) => {
-                    if let Some(index) = player.animations.get("Jump_Loop") {
-                        animation_player.start(*index).set_speed(*speed).repeat();
-                    }
-                }
+            play_state(state, set, player, locomotion, crossfade, animation_player);
+        }
+    }
+}
+
+/// Here the animations are actually being ran with their respective speed,
+/// fading in over `blend` seconds while the previous one fades out
+fn play_state(
+    state: &AnimationState,
+    set: &AnimationSet,
+    player: &Player,
+    locomotion: &Locomotion,
+    crossfade: &mut Crossfade,
+    animation_player: &mut AnimationPlayer,
+) {
+    let entry = set.get(state);
+    let node = match state {
+        AnimationState::StandIdle | AnimationState::Run(_) | AnimationState::Sprint(_) => {
+            Some(locomotion.node)
+        }
+        AnimationState::Attack(step) => player.attacks.get(*step).copied(),
+        _ => player.animations.get(&entry.clip).copied(),
+    };
+    let Some(node) = node else {
+        return;
+    };
+    if !crossfade.fade_to(node, entry.blend) {
+        return;
+    }
+    if node == locomotion.node {
+        for clip in locomotion.clips() {
+            animation_player.play(clip).repeat();
+        }
+    } else if player.attacks.contains(&node) {
+        // strikes are (re)started by the combat systems
+        animation_player.play(node).set_speed(entry.speed);
+    } else {
+        let animation = animation_player
+            .start(node)
+            .set_speed(entry.speed * state.speed());
+        if entry.looping {
+            animation.repeat();
+        }
+    }
+}
+
+/// 1D blend of idle/jog/sprint inside the locomotion node by the ground speed
+fn blend_locomotion(
+    cfg: &Config,
+    set: &AnimationSet,
+    speed: f32,
+    locomotion: &Locomotion,
+    animation_player: &mut AnimationPlayer,
+) {
+    let movement = &cfg.player.movement;
+    let weights = Locomotion::weights(
+        speed,
+        movement.idle_to_run_threshold,
+        movement.speed,
+        movement.speed * movement.sprint_factor,
+    );
+    let speeds = [
+        set.stand_idle.speed,
+        set.run.speed * ANIMATION_FACTOR * speed,
+        set.sprint.speed * ANIMATION_FACTOR * speed,
+    ];
+    for ((clip, weight), speed) in locomotion.clips().into_iter().zip(weights).zip(speeds) {
+        if let Some(animation) = animation_player.animation_mut(clip) {
+            animation.set_weight(weight).set_speed(speed);
+        }
+    }
+}
+
+/// Writes [`Crossfade`] weights to the playing clips and the locomotion node,
+/// stopping whatever faded out completely
+fn apply_crossfade(
+    dt: f32,
+    crossfade: &mut Crossfade,
+    locomotion: &Locomotion,
+    animation_player: &mut AnimationPlayer,
+    graph: &AnimationGraphHandle,
+    animation_graphs: &mut Assets<AnimationGraph>,
+) {
+    for node in crossfade.advance(dt) {
+        if node == locomotion.node {
+            for clip in locomotion.clips() {
+                animation_player.stop(clip);
             }
+        } else {
+            animation_player.stop(node);
+        }
+    }
+
+    let mut locomotion_weight = 0.0;
+    for &(node, weight) in crossfade.weights.iter() {
+        if node == locomotion.node {
+            locomotion_weight = weight;
+        } else if let Some(animation) = animation_player.animation_mut(node) {
+            animation.set_weight(weight);
+        }
+    }
+
+    // Blend node weight lives in the graph asset, only touch it when it changes
+    let current = animation_graphs
+        .get(&graph.0)
+        .and_then(|graph| graph.get(locomotion.node))
+        .map(|node| node.weight);
+    if current.is_some_and(|weight| weight != locomotion_weight) {
+        if let Some(node) = animation_graphs
+            .get_mut(&graph.0)
+            .and_then(|graph| graph.get_mut(locomotion.node))
+        {
+            node.weight = locomotion_weight;
+        }
+    }
+}
+
+/// Warns about clips from [`AnimationSet`] that the player model doesn't have.
+/// Runs on load and on every hot reload of `animations.ron`.
+pub fn validate_animation_set(
+    set: Res<AnimationSet>,
+    models: Res<Models>,
+    gltf_assets: Res<Assets<Gltf>>,
+) {
+    let Some(gltf) = gltf_assets.get(&models.player) else {
+        return;
+    };
+    let attacks = set
+        .attacks
+        .iter()
+        .map(|attack| ("attacks", &attack.animation));
+    for (state, entry) in set.entries().into_iter().chain(attacks) {
+        if !gltf.named_animations.contains_key(entry.clip.as_str()) {
+            warn!(
+                "animations.ron: clip '{}' for state '{state}' not found in the player model, available: {:?}",
+                entry.clip,
+                gltf.named_animations.keys().collect::<Vec<_>>()
+            );
         }
     }
 }
@@ -0,0 +1,248 @@
+use super::*;
+use bevy_tnua::builtins::TnuaBuiltinKnockback;
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (tick_hit_stun, tick_combo, cast_hitboxes)
+            .chain()
+            .before(animating)
+            .run_if(in_state(Screen::Gameplay).and(resource_exists::<AnimationSet>)),
+    )
+    .add_observer(handle_attack)
+    .add_observer(hitbox_on)
+    .add_observer(hitbox_off)
+    .add_observer(apply_damage)
+    .add_observer(respawn_player)
+    .add_observer(remove_destroyed);
+}
+
+fn handle_attack(
+    on: Trigger<Started<Attack>>,
+    set: Res<AnimationSet>,
+    mut players: Query<(&Player, &PlayerAnimator, &mut Combo), Without<HitStun>>,
+    mut animation_players: Query<&mut AnimationPlayer>,
+) {
+    let Ok((player, animator, mut combo)) = players.get_mut(on.target()) else {
+        return;
+    };
+    if set.attacks.is_empty() {
+        return;
+    }
+    // buffer the press, the next strike starts once this swing is over
+    if combo.attacking {
+        combo.queued = true;
+        return;
+    }
+    let Ok(mut animation_player) = animation_players.get_mut(animator.0) else {
+        return;
+    };
+    start_strike(set.attacks.len(), player, &mut combo, &mut animation_player);
+}
+
+/// Advances the combo and restarts the strike clip if it is still around from a previous swing
+fn start_strike(
+    len: usize,
+    player: &Player,
+    combo: &mut Combo,
+    animation_player: &mut AnimationPlayer,
+) {
+    let step = combo.next(len);
+    if let Some(animation) = player
+        .attacks
+        .get(step)
+        .and_then(|node| animation_player.animation_mut(*node))
+    {
+        animation.replay();
+    }
+}
+
+fn tick_hit_stun(
+    time: Res<Time>,
+    mut stunned: Query<(Entity, &mut HitStun)>,
+    mut commands: Commands,
+) {
+    for (entity, mut stun) in stunned.iter_mut() {
+        if stun.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<HitStun>();
+        }
+    }
+}
+
+/// Ends the swing once its clip is over and opens the combo window
+fn tick_combo(
+    time: Res<Time>,
+    cfg: Res<Config>,
+    set: Res<AnimationSet>,
+    mut players: Query<(&Player, &PlayerAnimator, &mut Combo), Without<HitStun>>,
+    mut animation_players: Query<&mut AnimationPlayer>,
+) {
+    for (player, animator, mut combo) in players.iter_mut() {
+        combo.window.tick(time.delta());
+        if !combo.attacking {
+            continue;
+        }
+        let Ok(mut animation_player) = animation_players.get_mut(animator.0) else {
+            continue;
+        };
+        let finished = player
+            .attacks
+            .get(combo.step)
+            .and_then(|node| animation_player.animation(*node))
+            .is_some_and(|animation| animation.is_finished());
+        if finished {
+            combo.end(cfg.player.combat.combo_window);
+            if combo.queued {
+                start_strike(set.attacks.len(), player, &mut combo, &mut animation_player);
+            }
+        }
+    }
+}
+
+/// Attack clip events are triggered on the [`AnimationPlayer`], find whose it is
+fn hitbox_on(on: Trigger<HitboxOn>, mut players: Query<(&PlayerAnimator, &mut Combo)>) {
+    for (_, mut combo) in players.iter_mut().filter(|(a, _)| a.0 == on.target()) {
+        if combo.attacking {
+            combo.hitbox = true;
+        }
+    }
+}
+
+fn hitbox_off(on: Trigger<HitboxOff>, mut players: Query<(&PlayerAnimator, &mut Combo)>) {
+    for (_, mut combo) in players.iter_mut().filter(|(a, _)| a.0 == on.target()) {
+        combo.hitbox = false;
+    }
+}
+
+/// Sphere cast in front of every attacking player while its hitbox is live.
+/// Each target is hit once per swing.
+fn cast_hitboxes(
+    cfg: Res<Config>,
+    spatial: SpatialQuery,
+    mut attackers: Query<(Entity, &Transform, &mut Combo)>,
+    players: Query<(), With<Player>>,
+    targets: Query<(), With<Health>>,
+    colliders: Query<&ColliderOf>,
+    mut commands: Commands,
+) {
+    let combat = &cfg.player.combat;
+    let shape = Collider::sphere(combat.hitbox_radius);
+    for (attacker, transform, mut combo) in attackers.iter_mut() {
+        if !combo.hitbox {
+            continue;
+        }
+        let forward = transform.forward();
+        let filter = SpatialQueryFilter::default().with_excluded_entities([attacker]);
+        let hits = spatial.shape_hits(
+            &shape,
+            transform.translation,
+            Quat::IDENTITY,
+            forward,
+            8,
+            &ShapeCastConfig::from_max_distance(combat.reach),
+            &filter,
+        );
+        for hit in hits {
+            // colliders may be children of the body holding the health
+            let target = colliders
+                .get(hit.entity)
+                .map(|collider| collider.body)
+                .unwrap_or(hit.entity);
+            if target == attacker || combo.hits.contains(&target) || !targets.contains(target) {
+                continue;
+            }
+            if !combat.friendly_fire && players.contains(target) {
+                continue;
+            }
+            combo.hits.push(target);
+            commands.trigger(OnDamage {
+                target,
+                source: attacker,
+                amount: combat.damage(combo.step),
+                knockback: forward * combat.knockback,
+            });
+        }
+    }
+}
+
+/// Takes health, shoves Tnua characters and stuns the target, interrupting its own combo
+fn apply_damage(
+    on: Trigger<OnDamage>,
+    cfg: Res<Config>,
+    mut targets: Query<(&mut Health, Option<&mut TnuaController>, Option<&mut Combo>)>,
+    mut commands: Commands,
+) {
+    let damage = on.event();
+    let Ok((mut health, controller, combo)) = targets.get_mut(damage.target) else {
+        return;
+    };
+
+    let taken = health.damage(damage.amount);
+    info!(
+        "{} took {taken} damage from {}, health: {}/{}",
+        damage.target, damage.source, health.current, health.max
+    );
+    if taken > 0.0 && health.is_dead() {
+        info!("{} is down", damage.target);
+        commands.trigger(OnDeath {
+            target: damage.target,
+            source: damage.source,
+        });
+        return;
+    }
+
+    if let Some(mut controller) = controller {
+        controller.action(TnuaBuiltinKnockback {
+            shove: damage.knockback,
+            ..Default::default()
+        });
+    }
+    if let Some(mut combo) = combo {
+        combo.interrupt();
+    }
+    commands
+        .entity(damage.target)
+        .insert(HitStun(Timer::from_seconds(
+            cfg.player.combat.hit_stun,
+            TimerMode::Once,
+        )));
+}
+
+/// Downed players get back up at their spawn point with full health
+fn respawn_player(
+    on: Trigger<OnDeath>,
+    cfg: Res<Config>,
+    mut players: Query<(
+        &GameplayCtx,
+        &mut Health,
+        &mut Transform,
+        &mut LinearVelocity,
+        &mut Combo,
+    )>,
+    mut commands: Commands,
+) {
+    let Ok((ctx, mut health, mut transform, mut velocity, mut combo)) =
+        players.get_mut(on.event().target)
+    else {
+        return;
+    };
+    let offset = Vec3::X * SPAWN_SPREAD * ctx.slot as f32;
+    transform.translation = Vec3::from(cfg.player.spawn_pos) + offset;
+    velocity.0 = Vec3::ZERO;
+    health.current = health.max;
+    combo.interrupt();
+    commands.entity(on.event().target).remove::<HitStun>();
+    commands.trigger(OnNotify(format!("Player {} is down", ctx.slot + 1)));
+}
+
+/// Anything else with [`Health`], like a training dummy from the scene, is destroyed
+fn remove_destroyed(
+    on: Trigger<OnDeath>,
+    players: Query<(), With<Player>>,
+    mut commands: Commands,
+) {
+    let target = on.event().target;
+    if !players.contains(target) {
+        commands.entity(target).despawn();
+    }
+}
@@ -15,7 +15,6 @@ pub fn plugin(app: &mut App) {
     .add_observer(handle_sprint_out)
     .add_observer(handle_jump)
     .add_observer(handle_dash)
-    // .add_observer(handle_attack)
     .add_observer(crouch_in)
     .add_observer(crouch_out);
 }
@@ -24,14 +23,26 @@ pub fn plugin(app: &mut App) {
 /// <https://github.com/idanarye/bevy-tnua/blob/main/demos/src/character_control_systems/platformer_control_systems.rs>
 fn movement(
     cfg: Res<Config>,
-    actions: Single<&Actions<GameplayCtx>>,
     camera: Query<&Transform, With<SceneCamera>>,
-    mut player_query: Query<(&mut Player, &mut TnuaController, &mut StepTimer)>,
+    mut player_query: Query<(
+        &Actions<GameplayCtx>,
+        &PlayerCamera,
+        &mut Player,
+        &mut TnuaController,
+        &mut StepTimer,
+        Has<HitStun>,
+    )>,
 ) -> Result {
-    let actions = actions.into_inner();
-    for (player, mut controller, mut step_timer) in player_query.iter_mut() {
-        let cam_transform = camera.single()?;
-        let input_value = actions.value::<Navigate>()?.as_axis2d();
+    for (actions, player_cam, player, mut controller, mut step_timer, stunned) in
+        player_query.iter_mut()
+    {
+        let cam_transform = camera.get(player_cam.0)?;
+        // no control while recovering from a hit
+        let input_value = if stunned {
+            Vec2::ZERO
+        } else {
+            actions.value::<Navigate>()?.as_axis2d()
+        };
         let direction = cam_transform.movement_direction(input_value);
 
         let float_height = 0.5;
@@ -67,7 +78,7 @@ fn movement(
         // sprint step (x1.5): 0.354
         // step on sprint timer: 0.317
         let Some((_, basis_state)) = controller.concrete_basis::<TnuaBuiltinWalk>() else {
-            return Ok(());
+            continue;
         };
         let current_actual_speed = basis_state.running_velocity.length();
         if current_actual_speed > IDLE_TO_RUN_TRESHOLD {
@@ -149,12 +160,16 @@ fn handle_jump(
 fn handle_dash(
     on: Trigger<Started<Dash>>,
     cfg: Res<Config>,
-    actions: Single<&Actions<GameplayCtx>>,
     camera: Query<&Transform, With<SceneCamera>>,
-    mut player_query: Query<(&mut TnuaController, &TnuaSimpleAirActionsCounter)>,
+    mut player_query: Query<(
+        &Actions<GameplayCtx>,
+        &PlayerCamera,
+        &mut TnuaController,
+        &TnuaSimpleAirActionsCounter,
+    )>,
 ) -> Result {
-    let (mut controller, air_counter) = player_query.get_mut(on.target())?;
-    let cam_transform = camera.single()?;
+    let (actions, player_cam, mut controller, air_counter) = player_query.get_mut(on.target())?;
+    let cam_transform = camera.get(player_cam.0)?;
     let navigate = actions.value::<Navigate>()?.as_axis2d();
     let direction = cam_transform.movement_direction(navigate);
 
@@ -170,18 +185,13 @@ fn handle_dash(
     Ok(())
 }
 
-// fn handle_attack(on: Trigger<Started<Attack>>, mut commands: Commands) {
-//     let entity = on.target();
-//     // TODO: Hit
-// }
-
 pub fn crouch_in(
     on: Trigger<Started<Crouch>>,
     cfg: Res<Config>,
     mut player: Query<&mut Player, With<GameplayCtx>>,
     mut tnua: Query<(&mut TnuaAvian3dSensorShape, &mut Collider), With<Player>>,
 ) -> Result {
-    let (mut avian_sensor, mut collider) = tnua.single_mut()?;
+    let (mut avian_sensor, mut collider) = tnua.get_mut(on.target())?;
     let mut player = player.get_mut(on.target())?;
 
     collider.set_scale(Vec3::new(1.0, 0.5, 1.0), 4);
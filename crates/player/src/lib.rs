@@ -11,12 +11,15 @@ use models::*;
 use std::{f32::consts::PI, time::Duration};
 
 mod animation;
+mod combat;
 mod control;
 mod sound;
 
 pub use animation::*;
 
 pub const IDLE_TO_RUN_TRESHOLD: f32 = 0.01;
+/// Distance between co-op players on spawn
+const SPAWN_SPREAD: f32 = 2.0;
 
 /// This plugin handles player related stuff like movement, shooting
 /// Player logic is only active during the State `Screen::Playing`
@@ -25,6 +28,7 @@ pub fn plugin(app: &mut App) {
         ThirdPersonCameraPlugin,
         TnuaControllerPlugin::new(FixedUpdate),
         TnuaAvian3dPlugin::new(FixedUpdate),
+        combat::plugin,
         control::plugin,
         sound::plugin,
     ));
@@ -33,15 +37,22 @@ pub fn plugin(app: &mut App) {
         .add_systems(OnEnter(Screen::Gameplay), spawn_player)
         .add_systems(
             Update,
-            animating
-                .in_set(TnuaUserControlsSystemSet)
-                .run_if(in_state(Screen::Gameplay)),
+            (
+                animating
+                    .in_set(TnuaUserControlsSystemSet)
+                    .run_if(in_state(Screen::Gameplay).and(resource_exists::<AnimationSet>)),
+                validate_animation_set.run_if(
+                    resource_exists::<Models>.and(resource_exists_and_changed::<AnimationSet>),
+                ),
+            ),
         )
         .add_observer(player_post_spawn);
 }
 
+/// Spawns a player per [`Lobby`] slot, or a single one outside of co-op
 pub fn spawn_player(
     cfg: Res<Config>,
+    lobby: Res<Lobby>,
     models: Res<Models>,
     gltf_assets: Res<Assets<Gltf>>,
     mut commands: Commands,
@@ -53,9 +64,19 @@ pub fn spawn_player(
         return Ok(());
     };
 
+    for ctx in lobby.slots() {
+        spawn_one(&cfg, gltf, ctx, &mut commands);
+    }
+
+    Ok(())
+}
+
+fn spawn_one(cfg: &Config, gltf: &Gltf, ctx: GameplayCtx, commands: &mut Commands) {
     let player_rot = Quat::from_rotation_y(PI);
     let mesh = SceneRoot(gltf.scenes[0].clone());
-    let pos = Vec3::from(cfg.player.spawn_pos);
+    // spread co-op players a bit so they don't spawn inside each other
+    let offset = Vec3::X * SPAWN_SPREAD * ctx.slot as f32;
+    let pos = Vec3::from(cfg.player.spawn_pos) + offset;
     let pos = Transform::from_translation(pos).with_rotation(player_rot);
     let player = Player {
         id: Entity::PLACEHOLDER,
@@ -68,12 +89,13 @@ pub fn spawn_player(
     commands
         .spawn((
             StateScoped(Screen::Gameplay),
+            Name::new(format!("Player {}", ctx.slot + 1)),
             pos,
             player,
             ThirdPersonCameraTarget,
             // input context
             (
-                GameplayCtx,
+                ctx,
                 CurrentCtx(Context::Gameplay),
                 Actions::<GameplayCtx>::default(),
             ),
@@ -92,12 +114,17 @@ pub fn spawn_player(
             (
                 collider,
                 RigidBody::Dynamic,
+                // kept out of camera casts unless `player.camera.collision_layers` asks for it
+                CollisionLayers::new(GameLayer::Player, LayerMask::ALL),
                 Friction::ZERO.with_combine_rule(CoefficientCombine::Multiply),
             ),
             // other player related components
             (
                 JumpTimer(Timer::from_seconds(cfg.timers.jump, TimerMode::Repeating)),
                 StepTimer(Timer::from_seconds(cfg.timers.step, TimerMode::Repeating)),
+                Footsteps::default(),
+                Health::new(cfg.player.combat.health),
+                Combo::default(),
                 InheritedVisibility::default(), // silence the warning because of adding SceneRoot as a child
             ),
         ))
@@ -122,8 +149,6 @@ pub fn spawn_player(
             // DEBUG
         })
         .observe(player_post_spawn);
-
-    Ok(())
 }
 
 fn player_post_spawn(
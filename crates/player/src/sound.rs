@@ -1,94 +1,178 @@
 use super::*;
 use audio::*;
-use bevy_seedling::prelude::*;
+use bevy_seedling::{prelude::*, sample::Sample};
 use rand::prelude::*;
 
 pub fn plugin(app: &mut App) {
-    app.add_observer(movement_sound)
-        .add_observer(dash_sound)
-        .add_observer(jump_sound);
+    app.add_systems(
+        Update,
+        landing_sound.run_if(in_state(Screen::Gameplay).and(resource_exists::<FootstepSounds>)),
+    )
+    .add_observer(movement_sound)
+    .add_observer(dash_sound)
+    .add_observer(jump_sound);
 }
 
 fn movement_sound(
     on: Trigger<Fired<Navigate>>,
     time: Res<Time>,
+    cfg: Res<Config>,
     state: Res<GameState>,
-    settings: Res<Settings>,
-    sources: ResMut<AudioSources>,
-    tnua: Query<&TnuaController, With<Player>>,
-    actions: Single<&Actions<GameplayCtx>>,
+    sounds: Res<FootstepSounds>,
+    surfaces: Query<&SurfaceMaterial>,
+    parents: Query<&ChildOf>,
+    mut players: Query<(
+        &Player,
+        &TnuaController,
+        &Actions<GameplayCtx>,
+        &GlobalTransform,
+        &mut Footsteps,
+        &mut StepTimer,
+    )>,
     mut cmds: Commands,
-    mut step_timer: Query<&mut StepTimer, With<Player>>,
 ) -> Result {
     if state.muted || state.paused {
         return Ok(());
     }
 
-    let controller = tnua.get(on.target())?;
-    let mut step_timer = step_timer.get_mut(on.target())?;
-
-    let Some((_, basis)) = controller.concrete_basis::<TnuaBuiltinWalk>() else {
+    let (player, controller, actions, transform, mut footsteps, mut step_timer) =
+        players.get_mut(on.target())?;
+    let Some(ground) = ground(controller) else {
         return Ok(());
     };
 
     // WALK SOUND
-    if step_timer.tick(time.delta()).just_finished() && basis.standing_on_entity().is_some() {
-        let mut rng = thread_rng();
-        let i = rng.gen_range(0..sources.steps.len());
-        let handle = if actions.value::<Crouch>()?.as_bool() {
-            // TODO: select crouch steps
-            sources.steps[i].clone()
+    if step_timer.tick(time.delta()).just_finished() {
+        let kind = if actions.value::<Crouch>()?.as_bool() {
+            StepKind::Crouch
+        } else if player.speed > cfg.player.movement.speed {
+            StepKind::Sprint
         } else {
-            sources.steps[i].clone()
+            StepKind::Walk
         };
-        cmds.spawn((Sfx, SamplePlayer::new(handle).with_volume(settings.sfx())));
+        let surface = surface(ground, &surfaces, &parents);
+        if let Some(clip) = step(&sounds, surface, kind, &mut footsteps, transform) {
+            cmds.spawn(clip);
+        }
     }
 
     Ok(())
 }
 
+fn landing_sound(
+    state: Res<GameState>,
+    sounds: Res<FootstepSounds>,
+    surfaces: Query<&SurfaceMaterial>,
+    parents: Query<&ChildOf>,
+    mut players: Query<(&TnuaController, &GlobalTransform, &mut Footsteps), With<Player>>,
+    mut cmds: Commands,
+) {
+    for (controller, transform, mut footsteps) in players.iter_mut() {
+        let ground = ground(controller);
+        let landed = footsteps.airborne && ground.is_some();
+        footsteps.airborne = ground.is_none();
+        let Some(ground) = ground.filter(|_| landed && !state.muted && !state.paused) else {
+            continue;
+        };
+        let surface = surface(ground, &surfaces, &parents);
+        if let Some(clip) = step(&sounds, surface, StepKind::Land, &mut footsteps, transform) {
+            cmds.spawn(clip);
+        }
+    }
+}
+
 fn jump_sound(
-    _: Trigger<Started<Jump>>,
+    on: Trigger<Started<Jump>>,
     state: Res<GameState>,
-    settings: Res<Settings>,
-    sources: ResMut<AudioSources>,
-    // jump_timer: Query<&JumpTimer, With<Player>>,
+    sounds: Res<FootstepSounds>,
+    surfaces: Query<&SurfaceMaterial>,
+    parents: Query<&ChildOf>,
+    mut players: Query<(&TnuaController, &GlobalTransform, &mut Footsteps), With<Player>>,
     mut cmds: Commands,
 ) -> Result {
     if state.muted || state.paused {
         return Ok(());
     }
 
-    // let jump_timer = jump_timer.get(on.target())?;
-    // if jump_timer.just_finished() {
-    let mut rng = thread_rng();
-    let i = rng.gen_range(0..sources.steps.len());
-    let handle = sources.steps[i].clone();
-    cmds.spawn((Sfx, SamplePlayer::new(handle).with_volume(settings.sfx())));
-    // }
+    // push off the ground the player stands on
+    let (controller, transform, mut footsteps) = players.get_mut(on.target())?;
+    let surface = ground(controller)
+        .map(|ground| surface(ground, &surfaces, &parents))
+        .unwrap_or_default();
+    if let Some(clip) = step(&sounds, surface, StepKind::Walk, &mut footsteps, transform) {
+        cmds.spawn(clip);
+    }
 
     Ok(())
 }
 
 fn dash_sound(
-    _: Trigger<Started<Dash>>,
+    on: Trigger<Started<Dash>>,
     state: Res<GameState>,
-    settings: Res<Settings>,
-    sources: ResMut<AudioSources>,
-    // jump_timer: Query<&JumpTimer, With<Player>>,
+    sounds: Res<FootstepSounds>,
+    surfaces: Query<&SurfaceMaterial>,
+    parents: Query<&ChildOf>,
+    mut players: Query<(&TnuaController, &GlobalTransform, &mut Footsteps), With<Player>>,
     mut cmds: Commands,
 ) -> Result {
     if state.muted || state.paused {
         return Ok(());
     }
 
-    // let jump_timer = jump_timer.get(on.target())?;
-    // if jump_timer.just_finished() {
-    let mut rng = thread_rng();
-    let i = rng.gen_range(0..sources.steps.len());
-    let handle = sources.steps[i].clone();
-    cmds.spawn((Sfx, SamplePlayer::new(handle).with_volume(settings.sfx())));
-    // }
+    let (controller, transform, mut footsteps) = players.get_mut(on.target())?;
+    let surface = ground(controller)
+        .map(|ground| surface(ground, &surfaces, &parents))
+        .unwrap_or_default();
+    if let Some(clip) = step(
+        &sounds,
+        surface,
+        StepKind::Sprint,
+        &mut footsteps,
+        transform,
+    ) {
+        cmds.spawn(clip);
+    }
 
     Ok(())
 }
+
+/// Entity the character stands on, `None` in the air
+fn ground(controller: &TnuaController) -> Option<Entity> {
+    let (_, basis) = controller.concrete_basis::<TnuaBuiltinWalk>()?;
+    basis.standing_on_entity()
+}
+
+/// Surface of the ground collider or of its closest ancestor that has one
+fn surface(
+    ground: Entity,
+    surfaces: &Query<&SurfaceMaterial>,
+    parents: &Query<&ChildOf>,
+) -> SurfaceMaterial {
+    std::iter::once(ground)
+        .chain(parents.iter_ancestors(ground))
+        .find_map(|e| surfaces.get(e).ok().copied())
+        .unwrap_or_default()
+}
+
+/// One-shot footstep emitted from the player position, never the same clip twice in a row
+fn step(
+    sounds: &FootstepSounds,
+    surface: SurfaceMaterial,
+    kind: StepKind,
+    footsteps: &mut Footsteps,
+    transform: &GlobalTransform,
+) -> Option<impl Bundle> {
+    let clips = sounds.clips(surface, kind);
+    let candidates: Vec<&Handle<Sample>> = clips
+        .iter()
+        .filter(|clip| clips.len() < 2 || Some(clip.id()) != footsteps.last)
+        .collect();
+    let clip = (*candidates.choose(&mut thread_rng())?).clone();
+    footsteps.last = Some(clip.id());
+
+    Some((
+        SpatialSfx,
+        SamplePlayer::new(clip).with_volume(Volume::Linear(kind.volume())),
+        Transform::from_translation(transform.translation()),
+    ))
+}
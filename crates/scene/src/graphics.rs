@@ -0,0 +1,113 @@
+//! Applies [`Graphics`] settings to the sky lights and every [`SceneCamera`] with a skybox,
+//! and picks a preset from a short benchmark while the quality is [`Quality::Detect`].
+//! Render scale is handled by the main camera in the game crate.
+use super::*;
+use bevy::{
+    core_pipeline::{bloom::Bloom, smaa::Smaa},
+    ecs::schedule::common_conditions::any_match_filter,
+    pbr::{Atmosphere, CascadeShadowConfig},
+};
+
+/// Seconds to skip while shaders compile and assets upload
+const WARMUP_SECS: f32 = 2.0;
+const BENCHMARK_SECS: f32 = 5.0;
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            apply_graphics.run_if(
+                resource_exists_and_changed::<Settings>
+                    .or(any_match_filter::<Added<Atmosphere>>)
+                    .or(any_match_filter::<Added<DirectionalLight>>),
+            ),
+            detect_quality
+                .run_if(not(in_state(Screen::Splash)).and(not(in_state(Screen::Loading)))),
+        )
+            .run_if(resource_exists::<Config>),
+    );
+}
+
+fn apply_graphics(
+    cfg: Res<Config>,
+    settings: Res<Settings>,
+    mut lights: Query<
+        (&mut DirectionalLight, &mut CascadeShadowConfig),
+        Or<(With<Sun>, With<Moon>)>,
+    >,
+    cameras: Query<(Entity, Has<DistanceFog>), (With<SceneCamera>, With<Atmosphere>)>,
+    mut commands: Commands,
+) {
+    let graphics = &settings.graphics;
+    let cascades = cascades(graphics);
+    for (mut light, mut config) in lights.iter_mut() {
+        light.shadows_enabled = graphics.shadows;
+        *config = cascades.clone();
+    }
+
+    for (camera, fogged) in cameras.iter() {
+        let mut camera = commands.entity(camera);
+        camera.insert(atmosphere_settings(graphics.atmosphere));
+        if graphics.bloom {
+            camera.insert(Bloom::NATURAL);
+        } else {
+            camera.remove::<Bloom>();
+        }
+        match graphics.anti_aliasing {
+            AntiAliasing::Off => camera.insert(Msaa::Off).remove::<Smaa>(),
+            AntiAliasing::Msaa => camera.insert(Msaa::Sample4).remove::<Smaa>(),
+            // SMAA replaces MSAA, they don't work together
+            AntiAliasing::Smaa => camera.insert((Msaa::Off, Smaa::default())),
+        };
+        if !(cfg.physics.distance_fog && graphics.fog) {
+            camera.remove::<DistanceFog>();
+        } else if !fogged {
+            camera.insert(distance_fog(&cfg));
+        }
+    }
+}
+
+/// Frames counted after the warm up, reset whenever the quality isn't [`Quality::Detect`]
+#[derive(Default)]
+struct Benchmark {
+    elapsed: f32,
+    frames: u32,
+}
+
+/// Measures the frame rate with the settings [`Quality::Detect`] starts from and saves the preset it fits
+fn detect_quality(
+    time: Res<Time<Real>>,
+    mut benchmark: Local<Benchmark>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+) {
+    // picking another preset mid benchmark starts over the next time
+    if settings.graphics.quality != Quality::Detect {
+        *benchmark = Benchmark::default();
+        return;
+    }
+    benchmark.elapsed += time.delta_secs();
+    if benchmark.elapsed < WARMUP_SECS {
+        return;
+    }
+    benchmark.frames += 1;
+    let measured = benchmark.elapsed - WARMUP_SECS;
+    if measured < BENCHMARK_SECS {
+        return;
+    }
+
+    let fps = benchmark.frames as f32 / measured;
+    let quality = Quality::from_fps(fps);
+    settings.graphics = Graphics::preset(quality);
+    info!("graphics benchmark: {fps:.0} fps, picked {quality:?}");
+    if let Err(e) = settings.save() {
+        error!(
+            "unable to write settings to '{}': {e}",
+            Settings::path().display()
+        );
+    }
+    commands.trigger(OnNotify(format!(
+        "Graphics set to {} ({fps:.0} fps measured)",
+        quality.as_str()
+    )));
+}
@@ -4,9 +4,12 @@ use bevy::prelude::*;
 use bevy_skein::SkeinPlugin;
 use models::*;
 
+mod graphics;
 mod skybox;
+mod weather;
 
 pub use skybox::*;
+pub use weather::*;
 
 /// This plugin handles loading and saving scenes
 /// Scene logic is only active during the State `Screen::Playing`
@@ -15,18 +18,29 @@ pub fn plugin(app: &mut App) {
         PhysicsPlugins::default(),
         SkeinPlugin::default(),
         bevy_fix_gltf_coordinate_system::FixGltfCoordinateSystemPlugin,
+        graphics::plugin,
         skybox::plugin,
+        weather::plugin,
     ))
-    .add_systems(OnEnter(Screen::Title), setup);
+    .add_systems(OnEnter(Screen::Title), setup)
+    .add_systems(Update, respawn_level.run_if(resource_exists::<Models>));
 }
 
-pub fn setup(models: Res<Models>, gltf_assets: Res<Assets<Gltf>>, mut commands: Commands) {
+pub fn setup(
+    cfg: Res<Config>,
+    models: Res<Models>,
+    gltf_assets: Res<Assets<Gltf>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
     let Some(scene) = gltf_assets.get(&models.scene) else {
         return;
     };
-    commands.spawn((
-        SceneRoot(scene.scenes[0].clone()),
-        Transform::from_scale(Vec3::splat(1.0)),
+    commands.spawn(level(scene)).with_child(boombox(
+        &cfg,
+        meshes.add(Cuboid::new(0.6, 0.4, 0.3)),
+        materials.add(Color::srgb(0.8, 0.2, 0.3)),
     ));
 
     // to see something when suns go away
@@ -36,3 +50,53 @@ pub fn setup(models: Res<Models>, gltf_assets: Res<Assets<Gltf>>, mut commands:
         ..Default::default()
     });
 }
+
+fn level(scene: &Gltf) -> impl Bundle {
+    (
+        Level,
+        SceneRoot(scene.scenes[0].clone()),
+        Transform::from_scale(Vec3::splat(1.0)),
+    )
+}
+
+/// Dropped next to the player spawn, plays `music.boombox`
+fn boombox(cfg: &Config, mesh: Handle<Mesh>, material: Handle<StandardMaterial>) -> impl Bundle {
+    let spawn = Vec3::from(cfg.player.spawn_pos);
+    (
+        Name::new("Boombox"),
+        Boombox,
+        Mesh3d(mesh),
+        MeshMaterial3d(material),
+        RigidBody::Dynamic,
+        Collider::cuboid(0.6, 0.4, 0.3),
+        Transform::from_translation(spawn + Vec3::new(4.0, 0.0, 0.0)),
+    )
+}
+
+/// Spawned scene keeps the old meshes and colliders around when `scene.glb` changes on disk
+fn respawn_level(
+    models: Res<Models>,
+    modified: Res<ModifiedAssets>,
+    gltf_assets: Res<Assets<Gltf>>,
+    levels: Query<Entity, With<Level>>,
+    boomboxes: Query<Entity, With<Boombox>>,
+    mut commands: Commands,
+) {
+    if levels.is_empty() || !modified.contains(&models.scene) {
+        return;
+    }
+    let Some(scene) = gltf_assets.get(&models.scene) else {
+        return;
+    };
+    info!("respawning level scene");
+    // the boombox isn't part of the scene file, it moves over to the new level
+    for boombox in boomboxes.iter() {
+        commands.entity(boombox).remove::<ChildOf>();
+    }
+    for entity in levels.iter() {
+        commands.entity(entity).despawn();
+    }
+    commands
+        .spawn(level(scene))
+        .add_children(&boomboxes.iter().collect::<Vec<_>>());
+}
@@ -1,36 +1,47 @@
 use super::*;
 use bevy::{
     core_pipeline::{bloom::Bloom, tonemapping::Tonemapping},
-    pbr::{Atmosphere, AtmosphereSettings, CascadeShadowConfigBuilder, light_consts::lux},
+    pbr::{
+        Atmosphere, AtmosphereSettings, CascadeShadowConfig, CascadeShadowConfigBuilder,
+        light_consts::lux,
+    },
     render::camera::Exposure,
 };
+use std::f32::consts::PI;
 
 pub fn plugin(app: &mut App) {
-    app.add_systems(Update, sun_cycle.run_if(in_state(Screen::Gameplay)));
+    app.init_resource::<TimeOfDay>()
+        .add_systems(OnEnter(Screen::Gameplay), start_day)
+        .add_systems(
+            Update,
+            (
+                advance_time.run_if(in_state(Screen::Gameplay)),
+                apply_time_of_day,
+            )
+                .chain()
+                .run_if(resource_exists::<Config>),
+        )
+        .add_observer(time_command);
 }
 
 /// Mainly this example:
 /// <https://bevyengine.org/examples/3d-rendering/atmosphere/>
 pub fn add_skybox_to_camera(
     cfg: Res<Config>,
+    settings: Res<Settings>,
     mut commands: Commands,
     mut camera: Query<Entity, With<SceneCamera>>,
 ) -> Result {
     let camera = camera.single_mut()?;
-
-    let cascade_shadow_config = CascadeShadowConfigBuilder {
-        first_cascade_far_bound: 0.3,
-        maximum_distance: cfg.physics.shadow_distance,
-        ..default()
-    }
-    .build();
+    let graphics = &settings.graphics;
+    let cascade_shadow_config = cascades(graphics);
 
     // Sun
     commands.spawn((
         StateScoped(Screen::Gameplay),
         DirectionalLight {
             color: SUN,
-            shadows_enabled: true,
+            shadows_enabled: graphics.shadows,
             illuminance: lux::FULL_DAYLIGHT,
             ..Default::default()
         },
@@ -44,7 +55,7 @@ pub fn add_skybox_to_camera(
         StateScoped(Screen::Gameplay),
         DirectionalLight {
             color: MOON,
-            shadows_enabled: true,
+            shadows_enabled: graphics.shadows,
             illuminance: lux::FULL_MOON_NIGHT,
             ..Default::default()
         },
@@ -54,37 +65,10 @@ pub fn add_skybox_to_camera(
     ));
 
     // Lighting
-    commands.entity(camera).insert((
-        // This is the component that enables atmospheric scattering for a camera
-        // TODO: manipulate ground_albedo depending on the angle of the sun
-        Atmosphere::EARTH,
-        // The scene is in units of 10km, so we need to scale up the
-        // aerial view lut distance and set the scene scale accordingly.
-        // Most usages of this feature will not need to adjust this.
-        AtmosphereSettings {
-            scene_units_to_m: 1.0,
-            aerial_view_lut_max_distance: 40_000.0, //  40 km for a vast scene
+    commands.entity(camera).insert(atmosphere(graphics));
 
-            // Higher resolution LUTs for smoother gradients and details
-            transmittance_lut_size: UVec2::new(512, 256), // Double resolution for smoother light transmission
-            sky_view_lut_size: UVec2::new(800, 400),      // Higher resolution for sky appearance
-            aerial_view_lut_size: UVec3::new(64, 64, 64), // More detailed aerial perspective
-
-            // Increased sample counts for better accuracy and less artifacts
-            transmittance_lut_samples: 60, // More samples for light transmission accuracy
-            multiscattering_lut_dirs: 128, // Double directions for multiscattering
-            multiscattering_lut_samples: 30, // More samples for multiscattering accuracy
-            sky_view_lut_samples: 24,      // More samples for sky appearance
-            aerial_view_lut_samples: 15,   // More samples for aerial view depth
-            ..Default::default()
-        },
-        Tonemapping::BlenderFilmic,
-        Exposure::OVERCAST,
-        Bloom::NATURAL,
-    ));
-
-    if cfg.physics.distance_fog {
-        commands.entity(camera).insert(distance_fog(cfg));
+    if cfg.physics.distance_fog && graphics.fog {
+        commands.entity(camera).insert(distance_fog(&cfg));
     }
 
     Ok(())
@@ -107,7 +91,72 @@ pub fn rm_skybox_from_camera(
     Ok(())
 }
 
-pub fn distance_fog(cfg: Res<Config>) -> impl Bundle {
+/// Camera components for atmospheric scattering, shared by every [`SceneCamera`].
+/// Bloom is left out below [`Graphics::bloom`] by `apply_graphics`.
+pub fn atmosphere(graphics: &Graphics) -> impl Bundle {
+    (
+        // This is the component that enables atmospheric scattering for a camera,
+        // its ground albedo follows the time of day in `apply_time_of_day`
+        Atmosphere::EARTH,
+        atmosphere_settings(graphics.atmosphere),
+        Tonemapping::BlenderFilmic,
+        Exposure::OVERCAST,
+        Bloom::NATURAL,
+    )
+}
+
+pub fn atmosphere_settings(detail: Detail) -> AtmosphereSettings {
+    // The scene is in units of 10km, so we need to scale up the
+    // aerial view lut distance and set the scene scale accordingly.
+    // Most usages of this feature will not need to adjust this.
+    let settings = AtmosphereSettings {
+        scene_units_to_m: 1.0,
+        aerial_view_lut_max_distance: 40_000.0, //  40 km for a vast scene
+        ..Default::default()
+    };
+    match detail {
+        // Half of the default resolution and samples, banding shows in sunsets
+        Detail::Low => AtmosphereSettings {
+            transmittance_lut_size: UVec2::new(128, 64),
+            sky_view_lut_size: UVec2::new(200, 100),
+            aerial_view_lut_size: UVec3::new(16, 16, 16),
+            transmittance_lut_samples: 20,
+            multiscattering_lut_dirs: 32,
+            multiscattering_lut_samples: 10,
+            sky_view_lut_samples: 8,
+            aerial_view_lut_samples: 5,
+            ..settings
+        },
+        Detail::Medium => settings,
+        Detail::High => AtmosphereSettings {
+            // Higher resolution LUTs for smoother gradients and details
+            transmittance_lut_size: UVec2::new(512, 256), // Double resolution for smoother light transmission
+            sky_view_lut_size: UVec2::new(800, 400),      // Higher resolution for sky appearance
+            aerial_view_lut_size: UVec3::new(64, 64, 64), // More detailed aerial perspective
+
+            // Increased sample counts for better accuracy and less artifacts
+            transmittance_lut_samples: 60, // More samples for light transmission accuracy
+            multiscattering_lut_dirs: 128, // Double directions for multiscattering
+            multiscattering_lut_samples: 30, // More samples for multiscattering accuracy
+            sky_view_lut_samples: 24,      // More samples for sky appearance
+            aerial_view_lut_samples: 15,   // More samples for aerial view depth
+            ..settings
+        },
+    }
+}
+
+/// Shadow cascades of the sun and moon
+pub fn cascades(graphics: &Graphics) -> CascadeShadowConfig {
+    CascadeShadowConfigBuilder {
+        num_cascades: graphics.cascades,
+        first_cascade_far_bound: 0.3,
+        maximum_distance: graphics.shadow_distance,
+        ..default()
+    }
+    .build()
+}
+
+pub fn distance_fog(cfg: &Config) -> impl Bundle {
     DistanceFog {
         color: Color::srgba(0.35, 0.48, 0.66, 1.0),
         directional_light_color: Color::srgba(1.0, 0.95, 0.85, 0.5),
@@ -120,17 +169,77 @@ pub fn distance_fog(cfg: Res<Config>) -> impl Bundle {
     }
 }
 
-fn sun_cycle(
+/// New games start at `start_hour`, saves restore their own hour afterwards
+pub fn start_day(cfg: Res<Config>, mut time_of_day: ResMut<TimeOfDay>) {
+    *time_of_day = TimeOfDay {
+        hour: cfg.time_of_day.start_hour,
+        paused: false,
+    };
+}
+
+fn advance_time(time: Res<Time>, cfg: Res<Config>, mut time_of_day: ResMut<TimeOfDay>) {
+    time_of_day.advance(time.delta_secs(), cfg.time_of_day.day_length);
+}
+
+/// Sun and moon face each other across the sky, at 6 the sun rises from the horizon.
+/// `Nimbus` circles them around the horizon instead.
+pub(crate) fn apply_time_of_day(
+    cfg: Res<Config>,
     settings: Res<Settings>,
-    mut sky_lights: Query<&mut Transform, Or<(With<Moon>, With<Sun>)>>,
-    time: Res<Time>,
+    time_of_day: Res<TimeOfDay>,
+    mut ambient: ResMut<AmbientLight>,
+    mut sun: Query<(&mut Transform, &mut DirectionalLight), (With<Sun>, Without<Moon>)>,
+    mut moon: Query<(&mut Transform, &mut DirectionalLight), (With<Moon>, Without<Sun>)>,
+    mut cameras: Query<(&mut Exposure, Option<&mut Atmosphere>), With<SceneCamera>>,
+) {
+    let day = &cfg.time_of_day;
+    let hour = time_of_day.hour;
+    let angle = -(hour - 6.0) * PI / 12.0;
+    let rotation = |angle: f32| match settings.sun_cycle {
+        SunCycle::DayNight => Quat::from_rotation_x(angle),
+        SunCycle::Nimbus => Quat::from_rotation_y(angle),
+    };
+
+    for (mut transform, mut light) in sun.iter_mut() {
+        transform.rotation = rotation(angle);
+        light.illuminance = day.sun_illuminance.sample(hour);
+        light.color = color_temperature(day.sun_temperature.sample(hour));
+    }
+    for (mut transform, mut light) in moon.iter_mut() {
+        transform.rotation = rotation(angle + PI);
+        light.illuminance = day.moon_illuminance.sample(hour);
+    }
+    for (mut exposure, atmosphere) in cameras.iter_mut() {
+        exposure.ev100 = day.exposure.sample(hour);
+        if let Some(mut atmosphere) = atmosphere {
+            atmosphere.ground_albedo = Vec3::splat(day.ground_albedo.sample(hour));
+        }
+    }
+    ambient.brightness = day.ambient_brightness.sample(hour);
+}
+
+/// `time` prints the clock, `time set <hour>`, `time pause` and `time resume` control it
+fn time_command(
+    on: Trigger<OnConsoleCommand>,
+    mut time_of_day: ResMut<TimeOfDay>,
+    mut commands: Commands,
 ) {
-    match settings.sun_cycle {
-        SunCycle::DayNight => sky_lights
-            .iter_mut()
-            .for_each(|mut tf| tf.rotate_x(-time.delta_secs() * std::f32::consts::PI / 50.0)),
-        SunCycle::Nimbus => sky_lights
-            .iter_mut()
-            .for_each(|mut tf| tf.rotate_y(-time.delta_secs() * std::f32::consts::PI / 50.0)),
+    let mut args = on.event().split_whitespace();
+    if args.next() != Some("time") {
+        return;
+    }
+    match (args.next(), args.next().map(str::parse::<f32>)) {
+        (None, _) => {}
+        (Some("set"), Some(Ok(hour))) => time_of_day.set(hour),
+        (Some("pause"), None) => time_of_day.paused = true,
+        (Some("resume"), None) => time_of_day.paused = false,
+        _ => {
+            commands.trigger(OnNotify(
+                "usage: time [set <hour> | pause | resume]".to_string(),
+            ));
+            return;
+        }
     }
+    let paused = if time_of_day.paused { " (paused)" } else { "" };
+    commands.trigger(OnNotify(format!("{}{paused}", time_of_day.clock())));
 }
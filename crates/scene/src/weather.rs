@@ -0,0 +1,338 @@
+//! Weather: presets from `weather.ron` blended into each other, picked by the console,
+//! the [`WeatherZone`] a player is in or the looping schedule, in that order.
+//!
+//! The blended preset scales the time of day lights, tints the distance fog,
+//! thickens the atmosphere and keeps rain or snow falling around the main camera.
+//! Ambient loops are played by the game crate from [`WeatherState::ambient`].
+use super::*;
+use bevy::pbr::{Atmosphere, NotShadowCaster};
+use rand::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<WeatherState>()
+        .init_resource::<ParticleAssets>()
+        .add_systems(OnEnter(Screen::Title), start_weather)
+        .add_systems(OnEnter(Screen::Gameplay), start_weather)
+        .add_systems(OnExit(Screen::Gameplay), leave_zones)
+        .add_systems(
+            Update,
+            (
+                advance_schedule.run_if(in_state(Screen::Gameplay)),
+                blend_weather,
+                apply_weather.after(apply_time_of_day),
+                update_particles,
+            )
+                .chain()
+                .run_if(resource_exists::<WeatherSet>),
+        )
+        .add_observer(enter_zone)
+        .add_observer(exit_zone)
+        .add_observer(weather_command);
+}
+
+#[derive(Resource, Default)]
+pub struct WeatherState {
+    /// Set from the console, wins over zones and the schedule
+    pub forced: Option<Weather>,
+    /// Weather zones players are in as `(zone, player, weather)`, the latest entered wins
+    zones: Vec<(Entity, Entity, Weather)>,
+    /// Current entry of `schedule` and the seconds left of it
+    step: usize,
+    left: f32,
+    scheduled: Weather,
+    /// Weather being blended into
+    pub target: Weather,
+    /// Blended preset when the transition started
+    from: WeatherPreset,
+    to: WeatherPreset,
+    /// `0..=1` from `from` to `to`
+    blend: f32,
+}
+
+impl WeatherState {
+    pub fn wanted(&self) -> Weather {
+        self.forced
+            .or(self.zones.last().map(|(_, _, weather)| *weather))
+            .unwrap_or(self.scheduled)
+    }
+
+    pub fn current(&self) -> WeatherPreset {
+        self.from.lerp(&self.to, self.blend)
+    }
+
+    /// Ambient loop to play and its volume in `0..=1`, the old loop fades out before the new one fades in
+    pub fn ambient(&self) -> (Option<&str>, f32) {
+        if self.from.ambient == self.to.ambient {
+            (self.to.ambient.as_deref(), 1.0)
+        } else if self.blend < 0.5 {
+            (self.from.ambient.as_deref(), 1.0 - 2.0 * self.blend)
+        } else {
+            (self.to.ambient.as_deref(), 2.0 * self.blend - 1.0)
+        }
+    }
+
+    /// Particles of both blended presets with their count thinned out by the blend
+    fn particles(&self, kind: ParticleKind) -> Option<(&Particles, usize)> {
+        [(&self.to, self.blend), (&self.from, 1.0 - self.blend)]
+            .into_iter()
+            .filter_map(|(preset, weight)| Some((preset.particles.as_ref()?, weight)))
+            .filter(|(particles, _)| particles.kind == kind)
+            .map(|(particles, weight)| (particles, (particles.count as f32 * weight) as usize))
+            .max_by_key(|(_, count)| *count)
+    }
+
+    fn snap(&mut self, weather: Weather, set: &WeatherSet) {
+        self.target = weather;
+        self.to = set.preset(weather);
+        self.from = self.to.clone();
+        self.blend = 1.0;
+    }
+}
+
+/// Shared meshes and materials of the weather particles
+#[derive(Resource)]
+struct ParticleAssets {
+    rain: (Handle<Mesh>, Handle<StandardMaterial>),
+    snow: (Handle<Mesh>, Handle<StandardMaterial>),
+}
+
+impl FromWorld for ParticleAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let rain_mesh = meshes.add(Cuboid::new(0.01, 0.4, 0.01));
+        let snow_mesh = meshes.add(Sphere::new(0.03));
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        let particle = |color: Color| StandardMaterial {
+            base_color: color,
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        };
+        Self {
+            rain: (
+                rain_mesh,
+                materials.add(particle(Color::srgba(0.7, 0.75, 0.85, 0.4))),
+            ),
+            snow: (
+                snow_mesh,
+                materials.add(particle(Color::srgba(1.0, 1.0, 1.0, 0.9))),
+            ),
+        }
+    }
+}
+
+#[derive(Component)]
+struct WeatherParticle {
+    kind: ParticleKind,
+    /// Offsets the sway of snowflakes
+    phase: f32,
+}
+
+/// Title screen and new games start from the beginning of the schedule
+fn start_weather(set: Option<Res<WeatherSet>>, mut state: ResMut<WeatherState>) {
+    let Some(set) = set else {
+        return;
+    };
+    let (weather, secs) = set.schedule.first().copied().unwrap_or_default();
+    state.step = 0;
+    state.left = secs;
+    state.scheduled = weather;
+    let wanted = state.wanted();
+    state.snap(wanted, &set);
+}
+
+fn advance_schedule(time: Res<Time>, set: Res<WeatherSet>, mut state: ResMut<WeatherState>) {
+    if set.schedule.is_empty() {
+        return;
+    }
+    state.left -= time.delta_secs();
+    if state.left <= 0.0 {
+        state.step = (state.step + 1) % set.schedule.len();
+        let (weather, secs) = set.schedule[state.step];
+        state.scheduled = weather;
+        state.left = secs;
+    }
+}
+
+fn blend_weather(time: Res<Time>, set: Res<WeatherSet>, mut state: ResMut<WeatherState>) {
+    if set.is_changed() {
+        // edited presets apply right away
+        state.to = set.preset(state.target);
+    }
+    let wanted = state.wanted();
+    if wanted != state.target {
+        state.from = state.current();
+        state.to = set.preset(wanted);
+        state.target = wanted;
+        state.blend = 0.0;
+    }
+    if state.blend < 1.0 {
+        state.blend = (state.blend + time.delta_secs() / set.transition.max(f32::EPSILON)).min(1.0);
+    }
+}
+
+/// Runs after the time of day set the lights for this frame
+fn apply_weather(
+    state: Res<WeatherState>,
+    mut lights: Query<&mut DirectionalLight, Or<(With<Sun>, With<Moon>)>>,
+    mut cameras: Query<(&mut Atmosphere, Option<&mut DistanceFog>), With<SceneCamera>>,
+) {
+    let weather = state.current();
+    for mut light in lights.iter_mut() {
+        light.illuminance *= weather.light;
+    }
+
+    let (r, g, b) = weather.fog_color;
+    let fog_color = Srgba::rgb(r, g, b);
+    for (mut atmosphere, fog) in cameras.iter_mut() {
+        atmosphere.rayleigh_scattering = Atmosphere::EARTH.rayleigh_scattering * weather.rayleigh;
+        atmosphere.mie_scattering = Atmosphere::EARTH.mie_scattering * weather.mie;
+        atmosphere.mie_absorption = Atmosphere::EARTH.mie_absorption * weather.mie;
+        if let Some(mut fog) = fog {
+            fog.color = fog_color.into();
+            fog.falloff = FogFalloff::from_visibility_colors(
+                weather.fog_visibility,
+                fog_color,
+                fog_color.mix(&Srgba::WHITE, 0.6),
+            );
+        }
+    }
+}
+
+/// Particles wrap around inside a box following the main camera,
+/// so a few hundred of them are enough to fill the view
+fn update_particles(
+    time: Res<Time>,
+    state: Res<WeatherState>,
+    assets: Res<ParticleAssets>,
+    camera: Query<&GlobalTransform, With<MainCamera>>,
+    mut particles: Query<(Entity, &WeatherParticle, &mut Transform)>,
+    mut commands: Commands,
+) {
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let center = camera.translation();
+    let dt = time.delta_secs();
+    let mut rng = thread_rng();
+
+    for kind in [ParticleKind::Rain, ParticleKind::Snow] {
+        let wanted = state.particles(kind);
+        let count = wanted.map(|(_, count)| count).unwrap_or_default();
+        let mut alive = 0;
+        for (entity, particle, mut transform) in particles.iter_mut() {
+            if particle.kind != kind {
+                continue;
+            }
+            alive += 1;
+            let Some((settings, _)) = wanted.filter(|_| alive <= count) else {
+                commands.entity(entity).despawn();
+                continue;
+            };
+            let area = Vec3::from(settings.area);
+            transform.translation.y -= settings.fall_speed * dt;
+            if kind == ParticleKind::Snow {
+                let sway = (time.elapsed_secs() + particle.phase).sin();
+                transform.translation.x += sway * 0.5 * dt;
+            }
+            let offset = (transform.translation - center + area).rem_euclid(2.0 * area) - area;
+            transform.translation = center + offset;
+        }
+
+        let Some((settings, _)) = wanted else {
+            continue;
+        };
+        let area = Vec3::from(settings.area);
+        let (mesh, material) = match kind {
+            ParticleKind::Rain => &assets.rain,
+            ParticleKind::Snow => &assets.snow,
+        };
+        for _ in alive..count {
+            let offset = Vec3::new(
+                rng.gen_range(-area.x..=area.x),
+                rng.gen_range(-area.y..=area.y),
+                rng.gen_range(-area.z..=area.z),
+            );
+            commands.spawn((
+                Name::new("Weather Particle"),
+                WeatherParticle {
+                    kind,
+                    phase: rng.gen_range(0.0..std::f32::consts::TAU),
+                },
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material.clone()),
+                Transform::from_translation(center + offset),
+                NotShadowCaster,
+                Pickable::IGNORE,
+            ));
+        }
+    }
+}
+
+fn enter_zone(
+    on: Trigger<OnCollisionStart>,
+    zones: Query<&WeatherZone>,
+    players: Query<(), With<Player>>,
+    mut state: ResMut<WeatherState>,
+) {
+    let Ok(zone) = zones.get(on.target()) else {
+        return;
+    };
+    let event = on.event();
+    let Some(player) = [Some(event.collider), event.body]
+        .into_iter()
+        .flatten()
+        .find(|e| players.contains(*e))
+    else {
+        return;
+    };
+    state.zones.push((on.target(), player, zone.weather));
+}
+
+fn exit_zone(on: Trigger<OnCollisionEnd>, mut state: ResMut<WeatherState>) {
+    let event = on.event();
+    state.zones.retain(|(zone, player, _)| {
+        *zone != on.target() || (*player != event.collider && Some(*player) != event.body)
+    });
+}
+
+fn leave_zones(mut state: ResMut<WeatherState>) {
+    state.zones.clear();
+}
+
+/// `weather` prints the current weather, `weather <name>` forces one and `weather auto` goes back
+fn weather_command(
+    on: Trigger<OnConsoleCommand>,
+    mut state: ResMut<WeatherState>,
+    mut commands: Commands,
+) {
+    let mut args = on.event().split_whitespace();
+    if args.next() != Some("weather") {
+        return;
+    }
+    match (args.next(), args.next()) {
+        (None, _) => {}
+        (Some("auto"), None) => state.forced = None,
+        (Some(name), None) => match Weather::ALL.into_iter().find(|w| w.as_str() == name) {
+            Some(weather) => state.forced = Some(weather),
+            None => {
+                let names = Weather::ALL.map(|w| w.as_str()).join(" | ");
+                commands.trigger(OnNotify(format!("usage: weather [{names} | auto]")));
+                return;
+            }
+        },
+        _ => {
+            commands.trigger(OnNotify("usage: weather [<name> | auto]".to_string()));
+            return;
+        }
+    }
+    let forced = if state.forced.is_some() {
+        " (forced)"
+    } else {
+        ""
+    };
+    commands.trigger(OnNotify(format!(
+        "weather: {}{forced}",
+        state.wanted().as_str()
+    )));
+}
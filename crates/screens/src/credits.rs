@@ -4,10 +4,7 @@ use super::*;
 use bevy::ecs::spawn::SpawnIter;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(
-        OnEnter(Screen::Credits),
-        (start_credits_music, spawn_credits_screen),
-    );
+    app.add_systems(OnEnter(Screen::Credits), spawn_credits_screen);
 }
 
 #[hot]
@@ -69,23 +66,3 @@ fn grid(content: Vec<[String; 2]>) -> impl Bundle {
         Children::spawn(SpawnIter(content)),
     )
 }
-
-fn start_credits_music(
-    mut commands: Commands,
-    settings: Res<Settings>,
-    sources: ResMut<AudioSources>,
-    mut bg_music: Query<&mut PlaybackSettings, With<Music>>,
-) {
-    for mut s in bg_music.iter_mut() {
-        s.pause();
-    }
-    let handle = sources.bg_music.clone();
-    commands.spawn((
-        StateScoped(Screen::Credits),
-        Name::new("Credits Music"),
-        Music,
-        SamplePlayer::new(handle)
-            .with_volume(settings.music())
-            .looping(),
-    ));
-}
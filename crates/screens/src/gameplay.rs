@@ -8,15 +8,20 @@ pub(super) fn plugin(app: &mut App) {
             OnEnter(Screen::Gameplay),
             spawn_gameplay_ui.after(scene::setup),
         )
+        .add_systems(
+            Update,
+            update_score_label.run_if(in_state(Screen::Gameplay)),
+        )
         .add_observer(toggle_mute)
         .add_observer(toggle_pause)
         .add_observer(trigger_menu_toggle_on_esc)
         .add_observer(add_new_modal)
         .add_observer(pop_modal)
-        .add_observer(clear_modals);
+        .add_observer(clear_modals)
+        .add_observer(pop_modal_on_save);
 }
 
-fn spawn_gameplay_ui(mut cmds: Commands, textures: Res<Textures>, settings: Res<Settings>) {
+fn spawn_gameplay_ui(mut cmds: Commands, ui_assets: Res<UiAssets>, settings: Res<Settings>) {
     info!("settings on gameplay enter:{settings:?}");
     let opts = Opts::default().hidden().width(Vw(5.0)).height(Vw(5.0));
     cmds.spawn((
@@ -36,14 +41,29 @@ fn spawn_gameplay_ui(mut cmds: Commands, textures: Res<Textures>, settings: Res<
                     ..Default::default()
                 },
                 children![
-                    (icon(opts.clone().image(textures.pause.clone())), PauseIcon),
-                    (icon(opts.clone().image(textures.mute.clone())), MuteIcon),
+                    (icon(opts.clone().image(ui_assets.pause.clone())), PauseIcon),
+                    (icon(opts.clone().image(ui_assets.mute.clone())), MuteIcon),
                 ]
             ),
+            // score
+            (
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Vw(1.0),
+                    left: Vw(1.0),
+                    ..Default::default()
+                },
+                children![(label("Score: 0"), ScoreLabel)]
+            ),
         ],
     ));
 }
 
+fn update_score_label(state: Res<GameState>, mut label: Single<&mut Text, With<ScoreLabel>>) {
+    let score = state.score;
+    label.0 = format!("Score: {}  Best: {}", score.run, score.high);
+}
+
 fn toggle_pause(
     _: Trigger<OnPauseToggle>,
     mut time: ResMut<Time<Virtual>>,
@@ -69,16 +89,13 @@ fn toggle_mute(
     settings: ResMut<Settings>,
     mut state: ResMut<GameState>,
     mut label: Query<&mut Node, With<MuteIcon>>,
-    mut music: Single<&mut VolumeNode, (With<SamplerPool<Music>>, Without<SamplerPool<Sfx>>)>,
-    mut sfx: Single<&mut VolumeNode, (With<SamplerPool<Sfx>>, Without<SamplerPool<Music>>)>,
+    mut sfx: Single<&mut VolumeNode, With<SamplerPool<Sfx>>>,
 ) {
     if let Ok(mut node) = label.single_mut() {
         if state.muted {
-            music.volume = settings.music();
             sfx.volume = settings.sfx();
             node.display = Display::None;
         } else {
-            music.volume = Volume::SILENT;
             sfx.volume = Volume::SILENT;
             node.display = Display::Flex;
         }
@@ -100,6 +117,12 @@ fn click_pop_modal(_: Trigger<Pointer<Click>>, mut cmds: Commands) {
 fn click_spawn_settings(_: Trigger<Pointer<Click>>, mut cmds: Commands) {
     cmds.trigger(OnNewModal(Modal::Settings));
 }
+fn pop_modal_on_save(_: Trigger<OnSaveGame>, mut cmds: Commands) {
+    cmds.trigger(OnPopModal);
+}
+fn click_spawn_save(_: Trigger<Pointer<Click>>, mut cmds: Commands) {
+    cmds.trigger(OnNewModal(Modal::Save));
+}
 
 fn trigger_menu_toggle_on_esc(
     _: Trigger<Back>,
@@ -146,6 +169,7 @@ fn add_new_modal(
     match modal {
         Modal::Main => cmds.spawn(menu_modal()),
         Modal::Settings => cmds.spawn(settings_modal()),
+        Modal::Save => cmds.spawn(save_modal()),
     };
 
     state.modals.push(modal.clone());
@@ -156,6 +180,7 @@ fn pop_modal(
     screen: Res<State<Screen>>,
     menu_marker: Query<Entity, With<MenuModal>>,
     settings_marker: Query<Entity, With<SettingsModal>>,
+    save_marker: Query<Entity, With<SaveModal>>,
     mut cmds: Commands,
     mut state: ResMut<GameState>,
 ) {
@@ -179,6 +204,11 @@ fn pop_modal(
                 cmds.entity(menu).despawn();
             }
         }
+        Modal::Save => {
+            if let Ok(menu) = save_marker.single() {
+                cmds.entity(menu).despawn();
+            }
+        }
     }
 
     // respawn next in the modal stack
@@ -186,6 +216,7 @@ fn pop_modal(
         match modal {
             Modal::Main => cmds.spawn(menu_modal()),
             Modal::Settings => cmds.spawn(settings_modal()),
+            Modal::Save => cmds.spawn(save_modal()),
         };
     }
 
@@ -201,6 +232,7 @@ fn clear_modals(
     state: ResMut<GameState>,
     menu_marker: Query<Entity, With<MenuModal>>,
     settings_marker: Query<Entity, With<SettingsModal>>,
+    save_marker: Query<Entity, With<SaveModal>>,
     mut cmds: Commands,
 ) {
     for m in &state.modals {
@@ -215,6 +247,11 @@ fn clear_modals(
                     cmds.entity(modal).despawn();
                 }
             }
+            Modal::Save => {
+                if let Ok(modal) = save_marker.single() {
+                    cmds.entity(modal).despawn();
+                }
+            }
         }
     }
 }
@@ -225,6 +262,34 @@ fn settings_modal() -> impl Bundle {
     (StateScoped(Screen::Gameplay), SettingsModal, settings_ui())
 }
 
+fn save_modal() -> impl Bundle {
+    (
+        StateScoped(Screen::Gameplay),
+        SaveModal,
+        ui_root("Save Game"),
+        children![(
+            BorderColor(WHITEISH),
+            BackgroundColor(TRANSLUCENT),
+            Node {
+                border: UiRect::all(Px(2.0)),
+                padding: UiRect::all(Vw(5.0)),
+                row_gap: Vh(2.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            children![
+                header("Save"),
+                save_slots(true),
+                btn_small(
+                    Opts::new("back").width(Vw(5.0)).border(UiRect::DEFAULT),
+                    click_pop_modal
+                )
+            ]
+        )],
+    )
+}
+
 fn menu_modal() -> impl Bundle {
     let opts = Opts::new("Settings")
         .width(Vw(15.0))
@@ -265,6 +330,7 @@ fn menu_modal() -> impl Bundle {
                         ..default()
                     },
                     children![
+                        btn(opts.clone().text("Save"), click_spawn_save),
                         btn(opts.clone(), click_spawn_settings),
                         btn(opts.text("Main Menu"), click_to_menu)
                     ]
@@ -11,9 +11,11 @@ use ui::*;
 mod credits;
 mod gameplay;
 mod loading;
+mod lobby;
 mod settings;
 mod splash;
 mod title;
+mod tutorial;
 
 pub fn plugin(app: &mut App) {
     app.init_state::<Screen>();
@@ -22,7 +24,9 @@ pub fn plugin(app: &mut App) {
     app.add_plugins((
         splash::plugin,
         loading::plugin,
+        lobby::plugin,
         title::plugin,
+        tutorial::plugin,
         settings::plugin,
         credits::plugin,
         gameplay::plugin,
@@ -79,13 +83,28 @@ pub mod to {
     pub fn credits(_: Trigger<OnPress>, mut cmds: Commands) {
         cmds.trigger(OnGoTo(Screen::Credits));
     }
+    pub fn lobby(_: Trigger<OnPress>, mut cmds: Commands) {
+        cmds.trigger(OnGoTo(Screen::Lobby));
+    }
+    pub fn tutorial(_: Trigger<OnPress>, mut cmds: Commands) {
+        cmds.trigger(OnGoTo(Screen::Tutorial));
+    }
     pub fn gameplay_or_loading(
         _: Trigger<OnPress>,
+        settings: Res<Settings>,
         resource_handles: Res<ResourceHandles>,
+        mut lobby: ResMut<Lobby>,
         mut next_screen: ResMut<NextState<Screen>>,
     ) {
+        // "Play" is always single player
+        lobby.gamepads.clear();
         if resource_handles.is_all_done() {
-            next_screen.set(Screen::Gameplay);
+            // first run offers the tutorial
+            if settings.tutorial_done {
+                next_screen.set(Screen::Gameplay);
+            } else {
+                next_screen.set(Screen::Tutorial);
+            }
         } else {
             next_screen.set(Screen::Loading);
         }
@@ -1,10 +1,18 @@
 //! A loading screen during which game assets are loaded.
 //! This reduces stuttering, especially for audio on WASM.
+//!
+//! Shows progress over every tracked asset, and the failed ones with a retry if any fail.
 
 use super::*;
+use bevy::asset::{LoadState, UntypedAssetId, UntypedAssetLoadFailedEvent};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Loading), spawn_loading_screen)
+    app.init_resource::<Retrying>()
+        .add_systems(OnEnter(Screen::Loading), spawn_loading_screen)
+        .add_systems(
+            Update,
+            (update_progress, show_failures).run_if(in_state(Screen::Loading)),
+        )
         .add_systems(
             Update,
             continue_to_menu_screen.run_if(in_state(Screen::Loading).and(all_assets_loaded)),
@@ -15,10 +23,112 @@ fn spawn_loading_screen(mut commands: Commands) {
     commands.spawn((
         StateScoped(Screen::Loading),
         ui_root("loading screen"),
-        children![label("Loading...")],
+        children![
+            label("Loading..."),
+            // progress bar
+            (
+                Node {
+                    width: Vw(40.0),
+                    height: Vh(2.0),
+                    border: UiRect::all(Px(2.0)),
+                    ..default()
+                },
+                BorderColor(WHITEISH),
+                children![(
+                    LoadingBar,
+                    Node {
+                        width: Percent(0.0),
+                        height: Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(WHITEISH),
+                )]
+            ),
+            (label(""), LoadingLabel),
+        ],
     ));
 }
 
+fn update_progress(
+    server: Res<AssetServer>,
+    resource_handles: Res<ResourceHandles>,
+    mut bar: Single<&mut Node, With<LoadingBar>>,
+    mut label: Single<&mut Text, With<LoadingLabel>>,
+) {
+    let (loaded, total) = resource_handles.progress(&server);
+    let percent = if total == 0 {
+        100.0
+    } else {
+        100.0 * loaded as f32 / total as f32
+    };
+    bar.width = Percent(percent);
+    label.0 = match resource_handles.current(&server) {
+        Some(name) => format!("{loaded}/{total}: {name}"),
+        None => format!("{loaded}/{total}"),
+    };
+}
+
+/// Assets reloaded by "Retry" whose reload hasn't started yet, they still report the old failure
+#[derive(Resource, Default)]
+struct Retrying(Vec<UntypedAssetId>);
+
+/// Failed assets would keep us on the loading screen forever, show what went wrong instead
+fn show_failures(
+    server: Res<AssetServer>,
+    resource_handles: Res<ResourceHandles>,
+    error_screen: Query<(), With<LoadingErrorScreen>>,
+    mut retrying: ResMut<Retrying>,
+    mut failed_again: EventReader<UntypedAssetLoadFailedEvent>,
+    mut commands: Commands,
+) {
+    // a reload is underway once its asset leaves the failed state, or done if it failed anew
+    let failed_again: Vec<_> = failed_again.read().map(|event| event.id).collect();
+    retrying.0.retain(|id| {
+        !failed_again.contains(id)
+            && matches!(server.get_load_state(*id), Some(LoadState::Failed(_)))
+    });
+    if !error_screen.is_empty() || !retrying.0.is_empty() {
+        return;
+    }
+    let failed = resource_handles.failed(&server);
+    if failed.is_empty() {
+        return;
+    }
+
+    let details = failed
+        .iter()
+        .map(|(path, error)| format!("{path}\n{error}"))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    error!("failed to load assets:\n{details}");
+    commands.spawn((
+        StateScoped(Screen::Loading),
+        LoadingErrorScreen,
+        ui_root("loading error screen"),
+        BackgroundColor(TRANSLUCENT),
+        GlobalZIndex(1),
+        children![
+            header("Failed to load assets"),
+            label(details),
+            btn_big("Retry", retry),
+        ],
+    ));
+}
+
+fn retry(
+    _: Trigger<OnPress>,
+    server: Res<AssetServer>,
+    resource_handles: Res<ResourceHandles>,
+    error_screen: Query<Entity, With<LoadingErrorScreen>>,
+    mut retrying: ResMut<Retrying>,
+    mut commands: Commands,
+) {
+    retrying.0 = resource_handles.retry(&server);
+    for entity in error_screen.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
 fn continue_to_menu_screen(mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Title);
 }
@@ -0,0 +1,74 @@
+//! Local co-op lobby: every gamepad that presses "South" joins as the next player.
+
+use super::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Lobby), (reset_lobby, spawn_lobby_screen))
+        .add_systems(
+            Update,
+            (join_or_leave, update_lobby_label).run_if(in_state(Screen::Lobby)),
+        );
+}
+
+fn reset_lobby(mut lobby: ResMut<Lobby>) {
+    lobby.gamepads.clear();
+}
+
+fn spawn_lobby_screen(mut commands: Commands) {
+    commands.spawn((
+        StateScoped(Screen::Lobby),
+        ui_root("Lobby UI"),
+        BackgroundColor(TRANSLUCENT),
+        children![
+            header("Co-op"),
+            label("Press (A) to join, (B) to leave, Start to play"),
+            (label(""), LobbyLabel),
+            btn_big("Play", start_coop),
+            btn_big("Back", to::title),
+        ],
+    ));
+}
+
+fn join_or_leave(
+    gamepads: Query<(Entity, &Gamepad)>,
+    resource_handles: Res<ResourceHandles>,
+    mut lobby: ResMut<Lobby>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    for (entity, gamepad) in gamepads.iter() {
+        if gamepad.just_pressed(GamepadButton::South) && lobby.join(entity) {
+            info!("gamepad {entity} joined as player {}", lobby.gamepads.len());
+        }
+        if gamepad.just_pressed(GamepadButton::East) && lobby.leave(entity) {
+            info!("gamepad {entity} left the lobby");
+        }
+        if gamepad.just_pressed(GamepadButton::Start)
+            && lobby.is_coop()
+            && resource_handles.is_all_done()
+        {
+            next_screen.set(Screen::Gameplay);
+        }
+    }
+}
+
+fn update_lobby_label(lobby: Res<Lobby>, mut label: Single<&mut Text, With<LobbyLabel>>) {
+    label.0 = if lobby.is_coop() {
+        (1..=lobby.gamepads.len())
+            .map(|i| format!("Player {i} ready"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        "Waiting for players...".to_string()
+    };
+}
+
+fn start_coop(
+    _: Trigger<OnPress>,
+    lobby: Res<Lobby>,
+    resource_handles: Res<ResourceHandles>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if lobby.is_coop() && resource_handles.is_all_done() {
+        next_screen.set(Screen::Gameplay);
+    }
+}
@@ -3,7 +3,8 @@ use super::*;
 /// This plugin is responsible for the game menu
 /// The menu is only drawn during the State [`Screen::Title`] and is removed when that state is exited
 pub fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Title), setup_menu);
+    app.add_systems(OnEnter(Screen::Title), setup_menu)
+        .add_observer(load_game);
 }
 
 fn setup_menu(mut commands: Commands) {
@@ -28,13 +29,21 @@ fn setup_menu(mut commands: Commands) {
             // https://github.com/bevyengine/bevy/issues/18953
             #[cfg(target_arch = "wasm32")]
             children![
+                btn_big("Continue", click_continue),
                 btn_big("Play", to::gameplay_or_loading),
+                btn_big("Load", click_load),
+                btn_big("Co-op", to::lobby),
+                btn_big("Tutorial", to::tutorial),
                 btn_big("Credits", to::credits),
                 btn_big("Settings", to::settings),
             ],
             #[cfg(not(target_arch = "wasm32"))]
             children![
+                btn_big("Continue", click_continue),
                 btn_big("Play", to::gameplay_or_loading),
+                btn_big("Load", click_load),
+                btn_big("Co-op", to::lobby),
+                btn_big("Tutorial", to::tutorial),
                 btn_big("Credits", to::credits),
                 btn_big("Settings", to::settings),
                 btn_big("Exit", exit_app)
@@ -43,6 +52,66 @@ fn setup_menu(mut commands: Commands) {
     ));
 }
 
+fn click_continue(_: Trigger<Pointer<Click>>, mut commands: Commands) {
+    match SaveData::latest() {
+        Some(slot) => commands.trigger(OnLoadGame(slot)),
+        None => info!(
+            "nothing to continue, no saves in '{}'",
+            SaveData::dir().display()
+        ),
+    }
+}
+
+fn click_load(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    opened: Query<Entity, With<SaveSlots>>,
+) {
+    // toggle the slot list
+    if let Ok(slots) = opened.single() {
+        commands.entity(slots).despawn();
+        return;
+    }
+    commands.spawn((
+        StateScoped(Screen::Title),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Vw(1.0),
+            right: Vw(1.0),
+            ..default()
+        },
+        save_slots(false),
+    ));
+}
+
+fn load_game(
+    on: Trigger<OnLoadGame>,
+    resource_handles: Res<ResourceHandles>,
+    mut commands: Commands,
+    mut lobby: ResMut<Lobby>,
+    mut state: ResMut<GameState>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let slot = **on.event();
+    let save = match SaveData::read(slot) {
+        Ok(save) => save,
+        Err(e) => {
+            error!("failed to load '{}': {e}", SaveData::path(slot).display());
+            return;
+        }
+    };
+    info!("loading save slot {}", slot + 1);
+    // loading is single player, co-op players past the first one are not restored
+    lobby.gamepads.clear();
+    state.reset();
+    commands.insert_resource(LoadedSave(save));
+    if resource_handles.is_all_done() {
+        next_screen.set(Screen::Gameplay);
+    } else {
+        next_screen.set(Screen::Loading);
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn exit_app(_: Trigger<Pointer<Click>>, mut app_exit: EventWriter<AppExit>) {
     app_exit.write(AppExit::Success);
@@ -0,0 +1,144 @@
+//! Tutorial intro, the steps themselves are prompted on top of gameplay.
+//! Each step completes when its input action fires.
+
+use super::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Tutorial), spawn_tutorial_screen)
+        .add_systems(
+            OnEnter(Screen::Gameplay),
+            spawn_prompt.run_if(resource_exists::<TutorialProgress>),
+        )
+        .add_systems(
+            Update,
+            update_prompt.run_if(
+                in_state(Screen::Gameplay)
+                    .and(resource_exists::<TutorialProgress>)
+                    .and(resource_exists::<TutorialSteps>),
+            ),
+        )
+        .add_systems(OnExit(Screen::Gameplay), stop_tutorial)
+        .add_observer(complete::<Navigate>(TutorialAction::Move))
+        .add_observer(complete::<Jump>(TutorialAction::Jump))
+        .add_observer(complete::<Sprint>(TutorialAction::Sprint))
+        .add_observer(complete::<Dash>(TutorialAction::Dash))
+        .add_observer(complete::<Crouch>(TutorialAction::Crouch))
+        .add_observer(complete::<Attack>(TutorialAction::Attack));
+}
+
+fn spawn_tutorial_screen(mut commands: Commands) {
+    commands.spawn((
+        StateScoped(Screen::Tutorial),
+        ui_root("Tutorial UI"),
+        BackgroundColor(TRANSLUCENT),
+        children![
+            header("Tutorial"),
+            label("Learn to move, jump, sprint, dash, crouch and fight"),
+            btn_big("Start", start_tutorial),
+            btn_big("Skip", skip_tutorial),
+            btn_big("Back", to::title),
+        ],
+    ));
+}
+
+fn start_tutorial(
+    _: Trigger<OnPress>,
+    mut commands: Commands,
+    mut lobby: ResMut<Lobby>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    lobby.gamepads.clear();
+    commands.insert_resource(TutorialProgress::default());
+    next_screen.set(Screen::Gameplay);
+}
+
+fn skip_tutorial(
+    _: Trigger<OnPress>,
+    mut settings: ResMut<Settings>,
+    mut lobby: ResMut<Lobby>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    finish(&mut settings);
+    lobby.gamepads.clear();
+    next_screen.set(Screen::Gameplay);
+}
+
+fn finish(settings: &mut Settings) {
+    settings.tutorial_done = true;
+    if let Err(e) = settings.save() {
+        error!(
+            "unable to write settings to '{}': {e}",
+            Settings::path().display()
+        );
+    }
+}
+
+fn spawn_prompt(mut commands: Commands) {
+    commands.spawn((
+        StateScoped(Screen::Gameplay),
+        Name::new("Tutorial Prompt"),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Vh(10.0),
+            width: Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        Pickable::IGNORE,
+        children![(label(""), TutorialPrompt)],
+    ));
+}
+
+fn update_prompt(
+    settings: Res<Settings>,
+    steps: Res<TutorialSteps>,
+    progress: Res<TutorialProgress>,
+    mut label: Single<&mut Text, With<TutorialPrompt>>,
+) {
+    let Some(step) = steps.steps.get(progress.step) else {
+        return;
+    };
+    label.0 = format!(
+        "{}/{}: {}",
+        progress.step + 1,
+        steps.steps.len(),
+        step.prompt(&settings.keybind)
+    );
+}
+
+/// Leaving gameplay midway abandons the tutorial, it can be replayed from the title
+fn stop_tutorial(mut commands: Commands) {
+    commands.remove_resource::<TutorialProgress>();
+}
+
+/// Advances the tutorial when `A` fires during the step waiting for `action`
+fn complete<A: InputAction>(
+    action: TutorialAction,
+) -> impl Fn(
+    Trigger<Started<A>>,
+    Option<ResMut<TutorialProgress>>,
+    Option<Res<TutorialSteps>>,
+    ResMut<Settings>,
+    Query<Entity, With<TutorialPrompt>>,
+    Commands,
+) {
+    move |_, progress, steps, mut settings, prompt, mut commands| {
+        let (Some(mut progress), Some(steps)) = (progress, steps) else {
+            return;
+        };
+        if steps.steps.get(progress.step).map(|step| step.action) != Some(action) {
+            return;
+        }
+
+        progress.step += 1;
+        if progress.step < steps.steps.len() {
+            return;
+        }
+        finish(&mut settings);
+        for entity in prompt.iter() {
+            commands.entity(entity).despawn();
+        }
+        commands.remove_resource::<TutorialProgress>();
+        commands.trigger(OnNotify("Tutorial complete!".to_string()));
+    }
+}
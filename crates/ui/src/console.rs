@@ -0,0 +1,96 @@
+//! Debug console: a single line opened with [`CONSOLE_KEY`], entered lines are triggered
+//! as [`OnConsoleCommand`] for whichever observer knows the command, answers come as toasts.
+use super::*;
+use bevy::input::{
+    ButtonState,
+    keyboard::{Key, KeyboardInput},
+};
+
+const CONSOLE_KEY: KeyCode = KeyCode::F1;
+const PROMPT: &str = "> ";
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            toggle_console,
+            type_command.run_if(any_with_component::<DebugConsole>),
+        )
+            .chain(),
+    );
+}
+
+/// Players stop reacting to keys while the console is open
+fn toggle_console(
+    keys: Res<ButtonInput<KeyCode>>,
+    screen: Res<State<Screen>>,
+    state: Res<GameState>,
+    console: Query<Entity, With<DebugConsole>>,
+    players: Query<Entity, With<Player>>,
+    mut commands: Commands,
+) {
+    if !keys.just_pressed(CONSOLE_KEY) {
+        return;
+    }
+    let ctx = if let Ok(console) = console.single() {
+        commands.entity(console).despawn();
+        if *screen.get() != Screen::Gameplay || !state.modals.is_empty() {
+            return;
+        }
+        Context::Gameplay
+    } else {
+        commands.spawn(console_line());
+        Context::Modal
+    };
+    for player in players.iter() {
+        commands.trigger(SwitchInputCtx::new(player, ctx.clone()));
+    }
+}
+
+fn console_line() -> impl Bundle {
+    (
+        Name::new("Debug Console"),
+        DebugConsole,
+        GlobalZIndex(i32::MAX - 1),
+        BackgroundColor(TRANSLUCENT),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Px(0.0),
+            width: Vw(100.0),
+            padding: UiRect::all(Vw(0.5)),
+            ..default()
+        },
+        Pickable::IGNORE,
+        children![(
+            ConsoleInput,
+            Text::new(PROMPT),
+            TextColor(WHITEISH),
+            Pickable::IGNORE
+        )],
+    )
+}
+
+fn type_command(
+    mut keys: EventReader<KeyboardInput>,
+    mut input: Single<&mut Text, With<ConsoleInput>>,
+    mut commands: Commands,
+) {
+    for key in keys.read().filter(|k| k.state == ButtonState::Pressed) {
+        match &key.logical_key {
+            Key::Character(c) => input.0.push_str(c),
+            Key::Space => input.0.push(' '),
+            Key::Backspace if input.0.len() > PROMPT.len() => {
+                input.0.pop();
+            }
+            Key::Enter => {
+                let line = input.0[PROMPT.len()..].trim().to_string();
+                input.0 = PROMPT.to_string();
+                if !line.is_empty() {
+                    info!("console: {line}");
+                    commands.trigger(OnConsoleCommand(line));
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -6,10 +6,13 @@ use bevy_seedling::prelude::*;
 use bevy_simple_subsecond_system::prelude::*;
 use models::*;
 
+#[cfg(feature = "dev_native")]
+mod console;
 mod interaction;
 mod opts;
 mod perf;
 mod prefabs;
+mod toast;
 mod widget;
 
 pub use interaction::*;
@@ -18,5 +21,12 @@ pub use prefabs::*;
 pub use widget::*;
 
 pub fn plugin(app: &mut App) {
-    app.add_plugins((perf::plugin, interaction::plugin, prefabs::plugin));
+    app.add_plugins((
+        perf::plugin,
+        interaction::plugin,
+        prefabs::plugin,
+        toast::plugin,
+    ));
+    #[cfg(feature = "dev_native")]
+    app.add_plugins(console::plugin);
 }
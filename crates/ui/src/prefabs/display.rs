@@ -0,0 +1,148 @@
+//! Display rows of the Video tab and the dialog reverting window changes
+//! the player doesn't keep in time, in case the new mode shows nothing.
+use super::settings::OptionLabel;
+use super::*;
+use bevy::window::Monitor;
+
+/// Seconds before an unconfirmed mode, resolution or monitor change is reverted
+const CONFIRM_SECS: f32 = 15.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        confirm_countdown.run_if(resource_exists::<PendingDisplay>),
+    );
+}
+
+impl OptionLabel for DisplayOption {
+    fn label(&self, settings: &Settings) -> String {
+        self.value(&settings.display)
+    }
+}
+
+/// Display settings from before the first unconfirmed change
+#[derive(Resource)]
+struct PendingDisplay {
+    previous: DisplaySettings,
+    timer: Timer,
+}
+
+pub(super) fn change_display(
+    option: DisplayOption,
+    step: i32,
+) -> impl Fn(
+    Trigger<Pointer<Click>>,
+    ResMut<Settings>,
+    Query<(Entity, &Monitor)>,
+    Option<ResMut<PendingDisplay>>,
+    Commands,
+) + Clone {
+    move |_: Trigger<Pointer<Click>>,
+          mut settings: ResMut<Settings>,
+          monitors: Query<(Entity, &Monitor)>,
+          pending: Option<ResMut<PendingDisplay>>,
+          mut commands: Commands| {
+        let previous = settings.display.clone();
+        settings
+            .display
+            .change(option, step, &monitor_list(monitors.iter()));
+        info!("display {option:?}: {}", option.value(&settings.display));
+        if !option.needs_confirm() || previous.same_window(&settings.display) {
+            return;
+        }
+        match pending {
+            // further changes restart the countdown, reverting goes back to the first settings
+            Some(mut pending) => pending.timer.reset(),
+            None => {
+                commands.insert_resource(PendingDisplay {
+                    previous,
+                    timer: Timer::from_seconds(CONFIRM_SECS, TimerMode::Once),
+                });
+                commands.spawn(confirm_dialog());
+            }
+        }
+    }
+}
+
+fn keep_display(
+    _: Trigger<Pointer<Click>>,
+    dialog: Query<Entity, With<DisplayConfirm>>,
+    mut commands: Commands,
+) {
+    commands.remove_resource::<PendingDisplay>();
+    for dialog in dialog.iter() {
+        commands.entity(dialog).despawn();
+    }
+}
+
+fn revert_display(
+    _: Trigger<Pointer<Click>>,
+    pending: Option<Res<PendingDisplay>>,
+    mut settings: ResMut<Settings>,
+    dialog: Query<Entity, With<DisplayConfirm>>,
+    mut commands: Commands,
+) {
+    if let Some(pending) = pending {
+        settings.display = pending.previous.clone();
+    }
+    commands.remove_resource::<PendingDisplay>();
+    for dialog in dialog.iter() {
+        commands.entity(dialog).despawn();
+    }
+}
+
+fn confirm_countdown(
+    time: Res<Time<Real>>,
+    mut pending: ResMut<PendingDisplay>,
+    mut settings: ResMut<Settings>,
+    mut label: Query<&mut Text, With<DisplayConfirmLabel>>,
+    dialog: Query<Entity, With<DisplayConfirm>>,
+    mut commands: Commands,
+) {
+    if pending.timer.tick(time.delta()).finished() {
+        info!("display change not confirmed, reverting");
+        settings.display = pending.previous.clone();
+        commands.remove_resource::<PendingDisplay>();
+        for dialog in dialog.iter() {
+            commands.entity(dialog).despawn();
+        }
+        return;
+    }
+    let left = pending.timer.remaining_secs().ceil();
+    for mut label in label.iter_mut() {
+        label.0 = format!("Keep these display settings?\nReverting in {left:.0}s");
+    }
+}
+
+/// Centered above the rest of the UI, only the toasts go over it
+fn confirm_dialog() -> impl Bundle {
+    (
+        Name::new("Display Confirm"),
+        DisplayConfirm,
+        GlobalZIndex(i32::MAX - 1),
+        BorderColor(WHITEISH),
+        BackgroundColor(TRANSLUCENT),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Vh(35.0),
+            left: Vw(30.0),
+            width: Vw(40.0),
+            border: UiRect::all(Px(2.0)),
+            padding: UiRect::all(Vw(1.0)),
+            row_gap: Px(20.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![
+            (label(""), DisplayConfirmLabel),
+            (
+                Node {
+                    column_gap: Px(30.0),
+                    ..default()
+                },
+                children![btn("Keep", keep_display), btn("Revert", revert_display)],
+            ),
+        ],
+    )
+}
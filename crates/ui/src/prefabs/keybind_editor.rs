@@ -4,7 +4,10 @@ use bevy::{
         relationship::RelatedSpawner,
         spawn::{SpawnWith, SpawnableList},
     },
-    input::{ButtonState, common_conditions::*, keyboard::KeyboardInput, mouse::MouseButtonInput},
+    input::{
+        ButtonState, common_conditions::*, gamepad::GamepadButtonChangedEvent,
+        keyboard::KeyboardInput, mouse::MouseButtonInput,
+    },
     ui::FocusPolicy,
 };
 use std::fmt::Write;
@@ -14,6 +17,13 @@ pub fn plugin(app: &mut App) {
         Update,
         (
             update_button_text,
+            (
+                update_stick_label,
+                update_invert_y_label,
+                update_deadzone_label,
+                update_sensitivity_label,
+            )
+                .run_if(resource_changed::<Settings>),
             (
                 cancel_binding.run_if(input_just_pressed(KeyCode::Escape)),
                 bind,
@@ -24,6 +34,21 @@ pub fn plugin(app: &mut App) {
 }
 
 pub fn keybind_editor(keybind: &Keybind) -> impl Bundle {
+    editor(actions_grid(keybind.clone()))
+}
+
+pub fn gamepad_editor(gamepad: &GamepadBind) -> impl Bundle {
+    editor((
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Vw(1.0),
+            ..Default::default()
+        },
+        children![gamepad_actions_grid(gamepad.clone()), sticks_grid(gamepad)],
+    ))
+}
+
+fn editor(content: impl Bundle) -> impl Bundle {
     // We use separate root node to let dialogs cover the whole UI.
     (
         Node {
@@ -41,7 +66,7 @@ pub fn keybind_editor(keybind: &Keybind) -> impl Bundle {
                 ..Default::default()
             },
             children![
-                actions_grid(keybind.clone()),
+                content,
                 (
                     Node {
                         align_items: AlignItems::End,
@@ -64,20 +89,16 @@ pub fn keybind_editor(keybind: &Keybind) -> impl Bundle {
 
 /// Creates [`SettingsField`] from passed field.
 ///
-/// Strips everything before first `.` in order to turn "settings.field_name" into just "field_name".
+/// Keeps the whole path, so the local binding has to be named after the [`Settings`] field,
+/// e.g. `keybind.forward` turns into "keybind.forward".
 macro_rules! settings_field {
     ($path:expr) => {{
         let _validate_field = &$path;
-        let full_path = stringify!($path);
-        let field_name = full_path
-            .split_once('.')
-            .map(|(_, s)| s)
-            .unwrap_or(full_path);
-        SettingsField(field_name)
+        SettingsField(stringify!($path))
     }};
 }
 
-/// Stores name of the [`Settings`] field.
+/// Stores path to the [`Settings`] field.
 ///
 /// Used to utilize reflection when applying settings.
 #[derive(Component, Clone, Copy)]
@@ -110,10 +131,75 @@ fn actions_grid(keybind: Keybind) -> impl Bundle {
             action_row("Crouch", settings_field!(keybind.crouch), keybind.crouch),
             action_row("Dash", settings_field!(keybind.dash), keybind.dash),
             action_row("Sprint", settings_field!(keybind.sprint), keybind.sprint),
+            action_row("Attack", settings_field!(keybind.attack), keybind.attack),
+        )),
+    )
+}
+
+fn gamepad_actions_grid(gamepad: GamepadBind) -> impl Bundle {
+    (
+        Node {
+            display: Display::Grid,
+            column_gap: Vw(1.0),
+            row_gap: Vw(1.0),
+            grid_template_columns: vec![GridTrack::auto(); INPUTS_PER_ACTION + 1],
+            ..Default::default()
+        },
+        Children::spawn((
+            action_row("Jump", settings_field!(gamepad.jump), gamepad.jump),
+            action_row("Crouch", settings_field!(gamepad.crouch), gamepad.crouch),
+            action_row("Dash", settings_field!(gamepad.dash), gamepad.dash),
+            action_row("Sprint", settings_field!(gamepad.sprint), gamepad.sprint),
+            action_row("Attack", settings_field!(gamepad.attack), gamepad.attack),
+            action_row("Menu", settings_field!(gamepad.menu), gamepad.menu),
         )),
     )
 }
 
+/// Stick assignment and tuning, applied right away unlike button bindings.
+fn sticks_grid(gamepad: &GamepadBind) -> impl Bundle {
+    (
+        Node {
+            display: Display::Grid,
+            column_gap: Vw(1.0),
+            row_gap: Vw(1.0),
+            grid_template_columns: vec![GridTrack::auto(); 4],
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        children![
+            Text::new("Movement stick"),
+            (
+                btn(gamepad.movement.as_str(), click_swap_sticks),
+                GamepadStickLabel
+            ),
+            Text::new("Invert Y"),
+            (
+                btn(invert_y_text(gamepad), click_toggle_invert_y),
+                InvertYLabel
+            ),
+            Text::new("Deadzone"),
+            (
+                Node::default(),
+                children![
+                    btn_small("-", lower_deadzone),
+                    (label(deadzone_text(gamepad)), GamepadDeadzoneLabel),
+                    btn_small("+", raise_deadzone),
+                ]
+            ),
+            Text::new("Sensitivity"),
+            (
+                Node::default(),
+                children![
+                    btn_small("-", lower_sensitivity),
+                    (label(sensitivity_text(gamepad)), GamepadSensitivityLabel),
+                    btn_small("+", raise_sensitivity),
+                ]
+            ),
+        ],
+    )
+}
+
 fn action_row(
     name: &'static str,
     field: SettingsField,
@@ -193,8 +279,10 @@ fn show_binding_dialog(
 
 fn bind(
     mut commands: Commands,
+    active_tab: Res<ActiveTab>,
     mut key_events: EventReader<KeyboardInput>,
     mut mouse_button_events: EventReader<MouseButtonInput>,
+    mut gamepad_button_events: EventReader<GamepadButtonChangedEvent>,
     dialog: Single<(Entity, &BindingDialog)>,
     root_entity: Single<Entity, (With<Node>, Without<ChildOf>)>,
     mut buttons: Query<(Entity, &Name, &mut InputButton)>,
@@ -202,13 +290,24 @@ fn bind(
     let keys = key_events
         .read()
         .filter(|event| event.state == ButtonState::Pressed)
-        .map(|event| event.key_code.into());
+        .map(|event| Input::from(event.key_code));
     let mouse_buttons = mouse_button_events
         .read()
         .filter(|event| event.state == ButtonState::Pressed)
-        .map(|event| event.button.into());
+        .map(|event| Input::from(event.button));
+    let mut gamepad_buttons = gamepad_button_events
+        .read()
+        .filter(|event| event.state == ButtonState::Pressed)
+        .map(|event| Input::from(event.button));
 
-    let Some(input) = keys.chain(mouse_buttons).next() else {
+    // Only accept inputs from the device the edited profile belongs to.
+    // Escape is handled by `cancel_binding` in both cases.
+    let input = if active_tab.0 == UiTab::Gamepad {
+        gamepad_buttons.next()
+    } else {
+        keys.chain(mouse_buttons).next()
+    };
+    let Some(input) = input else {
         return;
     };
 
@@ -306,10 +405,15 @@ fn cancel_replace_binding(
 fn apply(
     _trigger: Trigger<Pointer<Click>>,
     mut commands: Commands,
+    active_tab: Res<ActiveTab>,
     mut settings: ResMut<Settings>,
     buttons: Query<(&InputButton, &SettingsField)>,
 ) {
-    settings.keybind.clear();
+    // Only buttons of the active tab are spawned, so only its profile is rewritten.
+    match active_tab.0 {
+        UiTab::Gamepad => settings.gamepad.clear(),
+        _ => settings.keybind.clear(),
+    }
     for (button, field) in &buttons {
         if let Some(input) = button.input {
             // Utilize reflection to write by field name.
@@ -323,6 +427,129 @@ fn apply(
     commands.trigger(RebuildBindings);
 }
 
+// ============================ GAMEPAD STICKS ============================
+
+fn click_swap_sticks(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    mut settings: ResMut<Settings>,
+) {
+    settings.gamepad.swap_sticks();
+    commands.trigger(RebuildBindings);
+}
+
+fn click_toggle_invert_y(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    mut settings: ResMut<Settings>,
+) {
+    settings.gamepad.invert_y = !settings.gamepad.invert_y;
+    commands.trigger(RebuildBindings);
+}
+
+fn lower_deadzone(
+    _: Trigger<Pointer<Click>>,
+    cfg: Res<Config>,
+    mut commands: Commands,
+    mut settings: ResMut<Settings>,
+) {
+    let gamepad = &mut settings.gamepad;
+    gamepad.deadzone = (gamepad.deadzone - cfg.settings.step).max(GamepadBind::MIN_DEADZONE);
+    commands.trigger(RebuildBindings);
+}
+
+fn raise_deadzone(
+    _: Trigger<Pointer<Click>>,
+    cfg: Res<Config>,
+    mut commands: Commands,
+    mut settings: ResMut<Settings>,
+) {
+    let gamepad = &mut settings.gamepad;
+    gamepad.deadzone = (gamepad.deadzone + cfg.settings.step).min(GamepadBind::MAX_DEADZONE);
+    commands.trigger(RebuildBindings);
+}
+
+fn lower_sensitivity(
+    _: Trigger<Pointer<Click>>,
+    cfg: Res<Config>,
+    mut commands: Commands,
+    mut settings: ResMut<Settings>,
+) {
+    let gamepad = &mut settings.gamepad;
+    gamepad.sensitivity =
+        (gamepad.sensitivity - cfg.settings.step).max(GamepadBind::MIN_SENSITIVITY);
+    commands.trigger(RebuildBindings);
+}
+
+fn raise_sensitivity(
+    _: Trigger<Pointer<Click>>,
+    cfg: Res<Config>,
+    mut commands: Commands,
+    mut settings: ResMut<Settings>,
+) {
+    let gamepad = &mut settings.gamepad;
+    gamepad.sensitivity =
+        (gamepad.sensitivity + cfg.settings.step).min(GamepadBind::MAX_SENSITIVITY);
+    commands.trigger(RebuildBindings);
+}
+
+fn update_stick_label(
+    settings: Res<Settings>,
+    labels: Query<Entity, With<GamepadStickLabel>>,
+    children: Query<&Children>,
+    mut texts: Query<&mut Text>,
+) {
+    for label in &labels {
+        for child in children.iter_descendants(label) {
+            if let Ok(mut text) = texts.get_mut(child) {
+                text.0 = settings.gamepad.movement.as_str().to_owned();
+            }
+        }
+    }
+}
+
+fn update_invert_y_label(
+    settings: Res<Settings>,
+    labels: Query<Entity, With<InvertYLabel>>,
+    children: Query<&Children>,
+    mut texts: Query<&mut Text>,
+) {
+    for label in &labels {
+        for child in children.iter_descendants(label) {
+            if let Ok(mut text) = texts.get_mut(child) {
+                text.0 = invert_y_text(&settings.gamepad).to_owned();
+            }
+        }
+    }
+}
+
+fn update_deadzone_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut Text, With<GamepadDeadzoneLabel>>,
+) {
+    label.0 = deadzone_text(&settings.gamepad);
+}
+
+fn update_sensitivity_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut Text, With<GamepadSensitivityLabel>>,
+) {
+    label.0 = sensitivity_text(&settings.gamepad);
+}
+
+fn invert_y_text(gamepad: &GamepadBind) -> &'static str {
+    if gamepad.invert_y { "on" } else { "off" }
+}
+
+fn deadzone_text(gamepad: &GamepadBind) -> String {
+    let percent = (gamepad.deadzone * 100.0).round();
+    format!("{percent: <3}%")
+}
+
+fn sensitivity_text(gamepad: &GamepadBind) -> String {
+    format!("{:.1}", gamepad.sensitivity)
+}
+
 fn update_button_text(
     buttons: Query<(&InputButton, &Children), Changed<InputButton>>,
     mut text: Query<&mut Text>,
@@ -1,11 +1,14 @@
 use super::*;
 
+mod display;
 mod keybind_editor;
+mod save_slots;
 mod settings;
 
 pub use keybind_editor::*;
+pub use save_slots::*;
 pub use settings::*;
 
 pub fn plugin(app: &mut App) {
-    app.add_plugins((keybind_editor::plugin, settings::plugin));
+    app.add_plugins((display::plugin, keybind_editor::plugin, settings::plugin));
 }
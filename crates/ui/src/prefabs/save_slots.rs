@@ -0,0 +1,30 @@
+use super::*;
+use bevy::ecs::spawn::SpawnIter;
+
+/// A column with a button per save slot describing what is in it.
+/// Clicking a slot saves into it when `saving`, loads it otherwise.
+pub fn save_slots(saving: bool) -> impl Bundle {
+    let opts = Opts::new("").width(Vw(25.0));
+    let slots = (0..SAVE_SLOTS).map(move |slot| {
+        let click = move |_: Trigger<Pointer<Click>>, mut cmds: Commands| {
+            if saving {
+                cmds.trigger(OnSaveGame(slot));
+            } else {
+                cmds.trigger(OnLoadGame(slot));
+            }
+        };
+        btn(opts.clone().text(SaveData::describe(slot)), click)
+    });
+
+    (
+        Name::new("Save Slots"),
+        SaveSlots,
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Vh(1.0),
+            ..default()
+        },
+        Children::spawn(SpawnIter(slots)),
+    )
+}
@@ -1,4 +1,6 @@
+use super::display::change_display;
 use super::*;
+use bevy::ecs::system::IntoObserverSystem;
 use bevy::ui::Display as NodeDisplay;
 
 pub(super) fn plugin(app: &mut App) {
@@ -9,6 +11,8 @@ pub(super) fn plugin(app: &mut App) {
             update_music_volume_label,
             update_sfx_volume_label,
             update_fov_label,
+            update_option_labels::<GraphicsOption>,
+            update_option_labels::<DisplayOption>,
             update_tab_content.run_if(resource_changed::<ActiveTab>),
         ),
     );
@@ -25,7 +29,7 @@ pub fn save_settings(
 ) {
     match settings.save() {
         Ok(()) => {
-            info!("writing settings to '{SETTINGS_PATH}'");
+            info!("writing settings to '{}'", Settings::path().display());
             if let Ok(children) = root.single() {
                 for child in children.iter() {
                     if let Ok(grandchildren) = children_q.get(child) {
@@ -38,7 +42,10 @@ pub fn save_settings(
                 }
             }
         }
-        Err(e) => error!("unable to write settings to '{SETTINGS_PATH}': {e}"),
+        Err(e) => error!(
+            "unable to write settings to '{}': {e}",
+            Settings::path().display()
+        ),
     }
 }
 
@@ -67,15 +74,18 @@ fn update_tab_content(
                             commands.spawn(audio_grid()).insert(ChildOf(e));
                         }
                         UiTab::Video => {
-                            commands
-                                .spawn(video_grid(&settings.sun_cycle))
-                                .insert(ChildOf(e));
+                            commands.spawn(video_grid(&settings)).insert(ChildOf(e));
                         }
                         UiTab::Keybindings => {
                             commands
                                 .spawn(keybind_editor(&settings.keybind))
                                 .insert(ChildOf(e));
                         }
+                        UiTab::Gamepad => {
+                            commands
+                                .spawn(gamepad_editor(&settings.gamepad))
+                                .insert(ChildOf(e));
+                        }
                     }
                 } else {
                     node.border.bottom = Px(10.0);
@@ -93,28 +103,32 @@ fn lower_fov(
     _: Trigger<Pointer<Click>>,
     cfg: Res<Config>,
     mut settings: ResMut<Settings>,
-    mut world_model_projection: Single<&mut Projection>,
+    mut projections: Query<&mut Projection, With<SceneCamera>>,
 ) {
-    let Projection::Perspective(perspective) = world_model_projection.as_mut() else {
-        return;
-    };
     let new_fov = (settings.fov - cfg.settings.step.to_degrees()).max(cfg.settings.min_fov);
-    perspective.fov = new_fov.to_radians();
-    settings.fov = perspective.fov.to_degrees();
+    settings.fov = new_fov;
+    // every split screen camera shares the same fov
+    for mut projection in projections.iter_mut() {
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.fov = new_fov.to_radians();
+        }
+    }
 }
 
 fn raise_fov(
     _: Trigger<Pointer<Click>>,
     cfg: Res<Config>,
     mut settings: ResMut<Settings>,
-    mut world_model_projection: Single<&mut Projection>,
+    mut projections: Query<&mut Projection, With<SceneCamera>>,
 ) {
-    let Projection::Perspective(perspective) = world_model_projection.as_mut() else {
-        return;
-    };
     let new_fov = (settings.fov + cfg.settings.step.to_degrees()).min(cfg.settings.max_fov);
-    perspective.fov = new_fov.to_radians();
-    settings.fov = perspective.fov.to_degrees();
+    settings.fov = new_fov;
+    // every split screen camera shares the same fov
+    for mut projection in projections.iter_mut() {
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.fov = new_fov.to_radians();
+        }
+    }
 }
 
 fn update_fov_label(settings: Res<Settings>, mut label: Single<&mut Text, With<FovLabel>>) {
@@ -123,6 +137,58 @@ fn update_fov_label(settings: Res<Settings>, mut label: Single<&mut Text, With<F
     label.0 = text;
 }
 
+// GRAPHICS
+fn change_graphics(
+    option: GraphicsOption,
+    step: i32,
+) -> impl Fn(Trigger<Pointer<Click>>, ResMut<Settings>) + Clone {
+    move |_: Trigger<Pointer<Click>>, mut settings: ResMut<Settings>| {
+        settings.graphics.change(option, step);
+        info!("graphics {option:?}: {}", option.value(&settings.graphics));
+    }
+}
+
+fn detect_graphics(
+    _: Trigger<Pointer<Click>>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+) {
+    settings.graphics = Graphics::preset(Quality::Detect);
+    commands.trigger(OnNotify(
+        "Measuring performance, this takes a few seconds".to_string(),
+    ));
+}
+
+/// Settings row of an option enum, its label shows the current value
+pub(super) trait OptionLabel: Component + Copy {
+    fn label(&self, settings: &Settings) -> String;
+}
+
+impl OptionLabel for GraphicsOption {
+    fn label(&self, settings: &Settings) -> String {
+        self.value(&settings.graphics)
+    }
+}
+
+/// Option values are shown in the label itself or in the text of its button
+fn update_option_labels<O: OptionLabel>(
+    settings: Res<Settings>,
+    options: Query<(Entity, &O)>,
+    children: Query<&Children>,
+    mut texts: Query<&mut Text>,
+) {
+    for (entity, option) in options.iter() {
+        let value = option.label(&settings);
+        for entity in std::iter::once(entity).chain(children.iter_descendants(entity)) {
+            if let Ok(mut text) = texts.get_mut(entity) {
+                if text.0 != value {
+                    text.0 = value.clone();
+                }
+            }
+        }
+    }
+}
+
 // GENERAL
 fn lower_general(
     _: Trigger<Pointer<Click>>,
@@ -156,26 +222,15 @@ fn update_general_volume_label(
 }
 
 // MUSIC
-fn lower_music(
-    _: Trigger<Pointer<Click>>,
-    cfg: ResMut<Config>,
-    mut settings: ResMut<Settings>,
-    mut music: Single<&mut VolumeNode, (With<SamplerPool<Music>>, Without<SamplerPool<Sfx>>)>,
-) {
+fn lower_music(_: Trigger<Pointer<Click>>, cfg: ResMut<Config>, mut settings: ResMut<Settings>) {
+    // the music director applies it to the pool along with its crossfade
     let new_volume = (settings.sound.music - cfg.settings.step).max(cfg.settings.min_volume);
     settings.sound.music = new_volume;
-    music.volume = settings.music();
 }
 
-fn raise_music(
-    _: Trigger<Pointer<Click>>,
-    cfg: ResMut<Config>,
-    mut settings: ResMut<Settings>,
-    mut music: Single<&mut VolumeNode, (With<SamplerPool<Music>>, Without<SamplerPool<Sfx>>)>,
-) {
+fn raise_music(_: Trigger<Pointer<Click>>, cfg: ResMut<Config>, mut settings: ResMut<Settings>) {
     let new_volume = (settings.sound.music + cfg.settings.step).min(cfg.settings.max_volume);
     settings.sound.music = new_volume;
-    music.volume = settings.music();
 }
 
 fn update_music_volume_label(
@@ -369,9 +424,16 @@ fn tab_bar() -> impl Bundle {
                         UiTab::Video
                     ),
                     (
-                        btn(opts.text("Keybindings"), switch_to_tab(UiTab::Keybindings)),
+                        btn(
+                            opts.clone().text("Keybindings"),
+                            switch_to_tab(UiTab::Keybindings)
+                        ),
                         UiTab::Keybindings
                     ),
+                    (
+                        btn(opts.text("Gamepad"), switch_to_tab(UiTab::Gamepad)),
+                        UiTab::Gamepad
+                    ),
                 ],
             ),
         ],
@@ -395,7 +457,7 @@ fn navigation() -> impl Bundle {
     )
 }
 
-fn video_grid(cycle: &SunCycle) -> impl Bundle {
+fn video_grid(settings: &Settings) -> impl Bundle {
     (
         Name::new("Settings Video Grid"),
         Node {
@@ -409,7 +471,10 @@ fn video_grid(cycle: &SunCycle) -> impl Bundle {
         },
         children![
             label("Sun cycle"),
-            (btn(cycle.as_str(), click_toggle_sun_cycle), SunCycleLabel),
+            (
+                btn(settings.sun_cycle.as_str(), click_toggle_sun_cycle),
+                SunCycleLabel
+            ),
             label("FOV"),
             fov(),
             // TODO: do checkboxes
@@ -418,10 +483,71 @@ fn video_grid(cycle: &SunCycle) -> impl Bundle {
             #[cfg(feature = "dev_native")]
             label("debug ui"),
             #[cfg(feature = "dev_native")]
-            (btn("off", clock_toggle_debug_ui), DebugUiLabel)
+            (btn("off", clock_toggle_debug_ui), DebugUiLabel),
+            label("window mode"),
+            option_btn(settings, DisplayOption::Mode, change_display),
+            label("monitor"),
+            option_btn(settings, DisplayOption::Monitor, change_display),
+            label("resolution"),
+            option_knob(DisplayOption::Resolution, change_display),
+            label("vsync"),
+            option_btn(settings, DisplayOption::Vsync, change_display),
+            label("fps limit"),
+            option_knob(DisplayOption::FpsCap, change_display),
+            label("quality"),
+            option_btn(settings, GraphicsOption::Quality, change_graphics),
+            label("detect quality"),
+            btn("run", detect_graphics),
+            label("shadows"),
+            option_btn(settings, GraphicsOption::Shadows, change_graphics),
+            label("shadow distance"),
+            option_knob(GraphicsOption::ShadowDistance, change_graphics),
+            label("shadow cascades"),
+            option_knob(GraphicsOption::Cascades, change_graphics),
+            label("bloom"),
+            option_btn(settings, GraphicsOption::Bloom, change_graphics),
+            label("atmosphere"),
+            option_btn(settings, GraphicsOption::Atmosphere, change_graphics),
+            label("anti-aliasing"),
+            option_btn(settings, GraphicsOption::AntiAliasing, change_graphics),
+            label("render scale"),
+            option_knob(GraphicsOption::RenderScale, change_graphics),
+            label("fog"),
+            option_btn(settings, GraphicsOption::Fog, change_graphics),
         ],
     )
 }
+
+/// Toggles or cycles the option on click, `change` builds the observer for a step
+fn option_btn<O, I, B, M>(
+    settings: &Settings,
+    option: O,
+    change: impl Fn(O, i32) -> I,
+) -> impl Bundle
+where
+    O: OptionLabel,
+    I: IntoObserverSystem<Pointer<Click>, B, M>,
+    B: Bundle,
+{
+    (btn(option.label(settings), change(option, 1)), option)
+}
+
+fn option_knob<O, I, B, M>(option: O, change: impl Fn(O, i32) -> I) -> impl Bundle
+where
+    O: OptionLabel,
+    I: IntoObserverSystem<Pointer<Click>, B, M>,
+    B: Bundle,
+{
+    (
+        knobs_container(),
+        children![
+            btn_small("-", change(option, -1)),
+            knob_label(option),
+            btn_small("+", change(option, 1)),
+        ],
+    )
+}
+
 fn audio_grid() -> impl Bundle {
     (
         Name::new("Settings Grid"),
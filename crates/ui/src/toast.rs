@@ -0,0 +1,44 @@
+use super::*;
+
+/// Seconds a toast stays on screen
+const TOAST_SECS: f32 = 6.0;
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(Update, expire_toasts)
+        .add_observer(spawn_toast);
+}
+
+/// Shown at the top of the screen above any other UI, kept across screen changes
+fn spawn_toast(on: Trigger<OnNotify>, mut commands: Commands) {
+    commands.spawn((
+        Name::new("Toast"),
+        ToastTimer(Timer::from_seconds(TOAST_SECS, TimerMode::Once)),
+        GlobalZIndex(i32::MAX),
+        BorderColor(WHITEISH),
+        BackgroundColor(TRANSLUCENT),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Vh(2.0),
+            left: Vw(30.0),
+            width: Vw(40.0),
+            border: UiRect::all(Px(2.0)),
+            padding: UiRect::all(Vw(1.0)),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        Pickable::IGNORE,
+        children![label(on.event().0.clone())],
+    ));
+}
+
+fn expire_toasts(
+    time: Res<Time<Real>>,
+    mut toasts: Query<(Entity, &mut ToastTimer)>,
+    mut commands: Commands,
+) {
+    for (entity, mut timer) in toasts.iter_mut() {
+        if timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -22,19 +22,33 @@ fn main() {
             .chain(),
     );
 
+    // the window opens in the saved mode, the rest of the settings are loaded on the title screen.
+    // logging isn't set up yet, the title screen reads them again and logs what went wrong
+    let display = Settings::read()
+        .map(|read| read.settings.display)
+        .unwrap_or_default();
+    let mut primary_window = Window {
+        title: "Bevy Game".to_string(),
+        // Bind to canvas included in `index.html` for custom wasm js logic
+        // canvas: Some("#bevy".to_owned()),
+        fit_canvas_to_parent: true,
+        // Tells wasm not to override default event handling, like F5 and Ctrl+R
+        prevent_default_event_handling: false,
+        ..default()
+    };
+    // monitors aren't known yet, exclusive fullscreen gets its video mode once they are
+    // and a monitor or resolution they don't have is clamped then, see `DisplaySettings::clamp`
+    display.apply(&mut primary_window, None);
+    app.insert_resource(Settings {
+        display,
+        ..default()
+    });
     let window = WindowPlugin {
-        primary_window: Some(Window {
-            title: "Bevy Game".to_string(),
-            // Bind to canvas included in `index.html` for custom wasm js logic
-            // canvas: Some("#bevy".to_owned()),
-            fit_canvas_to_parent: true,
-            // Tells wasm not to override default event handling, like F5 and Ctrl+R
-            prevent_default_event_handling: false,
-            ..default()
-        }),
+        primary_window: Some(primary_window),
         ..default()
     };
     let assets = AssetPlugin {
+        file_path: asset_loading::ASSET_FOLDER.to_string(),
         meta_check: AssetMetaCheck::Never,
         ..default()
     };